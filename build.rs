@@ -0,0 +1,55 @@
+//! Generates the solution registry used to run solutions in-process (see `template::registry`).
+//! Every `src/bin/YYYY-DD.rs` file is included as a module of the main binary.
+use std::{env, fs, path::Path};
+
+fn main() {
+    // the registry is only compiled with the `in_process` feature. Without it, changes to solutions must not trigger a rebuild.
+    if env::var_os("CARGO_FEATURE_IN_PROCESS").is_none() {
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut days: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = stem.split_once('-')?;
+                    let (year, day): (u16, u8) = (year.parse().ok()?, day.parse().ok()?);
+                    (year >= 2015 && (1..=25).contains(&day))
+                        .then(|| (year, day, path.to_string_lossy().into_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (year, day, path) in &days {
+        modules.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code, unused_imports)]\nmod y{year}_day_{day:02};\n"
+        ));
+        entries.push_str(&format!(
+            "    Solution {{ year: advent_of_code::year!({year}), day: advent_of_code::day!({day}), run_parts: y{year}_day_{day:02}::run_parts }},\n"
+        ));
+    }
+
+    let registry = format!(
+        "// @generated by build.rs\nuse advent_of_code::template::registry::Solution;\n\n{modules}\npub static SOLUTIONS: &[Solution] = &[\n{entries}];\n"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out_path, registry).unwrap();
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(2015, 1);
 
 pub fn part_one(input: &str) -> Option<i32> {
     Some(input.chars().filter_map(|c| match c { '(' => Some(1i32), ')' => Some(-1i32), _ => None}).sum())
@@ -1,11 +1,12 @@
 use std::{num::ParseIntError, cmp};
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2015, 2);
 
 struct Dimension(u32, u32, u32);
 
-#[derive(Debug)]
-enum ParseError {
+#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
     InvalidStructure,
     NotANumber(ParseIntError),
 }
@@ -27,10 +28,10 @@ fn solve_one(dim: &Dimension) -> u32 {
     (2*l*w) + (2*w*h) + (2*h*l) + min
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let dims = input.lines().map(parse).collect::<Result<Vec<_>,_>>().ok()?;
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let dims = input.lines().map(parse).collect::<Result<Vec<_>,_>>()?;
 
-    Some(dims.iter().map(solve_one).sum())
+    Ok(dims.iter().map(solve_one).sum())
 }
 
 fn solve_two(dim: &Dimension) -> u32 {
@@ -44,10 +45,10 @@ fn solve_two(dim: &Dimension) -> u32 {
     min1 + min1 + min2 + min2 + (h*w*l)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let dims = input.lines().map(parse).collect::<Result<Vec<_>,_>>().ok()?;
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let dims = input.lines().map(parse).collect::<Result<Vec<_>,_>>()?;
 
-    Some(dims.iter().map(solve_two).sum())
+    Ok(dims.iter().map(solve_two).sum())
 }
 
 #[cfg(test)]
@@ -61,7 +62,7 @@ mod tests {
     #[case("1x1x10", 43)]
     fn test_part_one(#[case] input: &str, #[case] expected: u32) {
         let result = part_one(input);
-        assert_eq!(result, Some(expected));
+        assert_eq!(result, Ok(expected));
     }
 
     #[rstest]
@@ -69,6 +70,6 @@ mod tests {
     #[case("1x1x10", 14)]
     fn test_part_two(#[case] input: &str, #[case] expected: u32) {
         let result = part_two(input);
-        assert_eq!(result, Some(expected));
+        assert_eq!(result, Ok(expected));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2015, 3);
 
 type Coord = (i32, i32);
 
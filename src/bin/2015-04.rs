@@ -1,6 +1,6 @@
 use md5::Digest;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2015, 4);
 
 fn hash(s: &str, n: usize) -> Digest {
     let input = format!("{}{}", s, n);
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2015, 5);
 
 #[derive(Debug, PartialEq, Eq)]
 enum NaughtyReasonOne {
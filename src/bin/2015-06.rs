@@ -1,9 +1,9 @@
 use std::num::ParseIntError;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2015, 6, parse, part_one: [part_one_intervals]);
 
 #[derive(Debug, Copy, Clone)]
-struct Coord {
+pub struct Coord {
     x: usize,
     y: usize,
 }
@@ -89,19 +89,30 @@ impl Light for LightsOne {
 
 }
 
-#[derive(Debug)]
-enum ParseError {
+#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
     CommandNotUnderstood,
     NotANumber(ParseIntError),
     InvalidStructure,
 }
 
-enum Instruction {
+pub enum Instruction {
     TurnOn { start: Coord, end: Coord },
     TurnOff { start: Coord, end: Coord },
     Toggle { start: Coord, end: Coord },
 }
 
+impl Instruction {
+    fn coords(&self) -> (&Coord, &Coord) {
+        match self {
+            Instruction::TurnOn { start, end }
+            | Instruction::TurnOff { start, end }
+            | Instruction::Toggle { start, end } => (start, end),
+        }
+    }
+}
+
 fn parse_coord(input: &str) -> Result<Coord, ParseError> {
     let (x_str, y_str) = input.split_once(',').ok_or(ParseError::InvalidStructure)?;
 
@@ -143,10 +154,64 @@ fn parse_line(input: &str) -> Result<Instruction, ParseError> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input.lines().map(parse_line).collect()
+}
+
+pub fn part_one(instructions: &[Instruction]) -> Option<usize> {
     let mut lights = LightsOne::new();
 
-    generic_solution(input, &mut lights)
+    generic_solution(instructions, &mut lights)
+}
+
+/// Splits the grid into rectangles at the corners of all instructions, so that the lights in a rectangle always
+/// have the same state. Only the rectangles are switched, instead of every single light.
+pub fn part_one_intervals(instructions: &[Instruction]) -> Option<usize> {
+    let bounds = |axis: fn(&Coord) -> usize| {
+        let mut bounds: Vec<usize> = instructions
+            .iter()
+            .flat_map(|inst| {
+                let (start, end) = inst.coords();
+                [axis(start), axis(end) + 1]
+            })
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        bounds
+    };
+    let xs = bounds(|c| c.x);
+    let ys = bounds(|c| c.y);
+
+    let mut lights = vec![false; xs.len() * ys.len()];
+
+    for inst in instructions {
+        let (start, end) = inst.coords();
+        let x_range = xs.binary_search(&start.x).ok()?..xs.binary_search(&(end.x + 1)).ok()?;
+        let y_range = ys.binary_search(&start.y).ok()?..ys.binary_search(&(end.y + 1)).ok()?;
+
+        for y in y_range {
+            for light in &mut lights[y * xs.len()..][x_range.clone()] {
+                *light = match inst {
+                    Instruction::TurnOn { .. } => true,
+                    Instruction::TurnOff { .. } => false,
+                    Instruction::Toggle { .. } => !*light,
+                };
+            }
+        }
+    }
+
+    // the last bounds only end rectangles, so every light that is on has a next bound.
+    let count = lights
+        .iter()
+        .enumerate()
+        .filter(|(_, &on)| on)
+        .map(|(i, _)| {
+            let (x, y) = (i % xs.len(), i / xs.len());
+            (xs[x + 1] - xs[x]) * (ys[y + 1] - ys[y])
+        })
+        .sum();
+
+    Some(count)
 }
 
 struct LightsTwo(Vec<usize>);
@@ -186,28 +251,22 @@ impl Light for LightsTwo {
     }
 }
 
-fn generic_solution(input: &str, lights: &mut impl Light) -> Option<usize> {
-    let instructions: Vec<Instruction> = input
-        .lines()
-        .map(parse_line)
-        .collect::<Result<Vec<_>, _>>()
-        .expect("parses all lines");
-
+fn generic_solution(instructions: &[Instruction], lights: &mut impl Light) -> Option<usize> {
     for inst in instructions {
         match inst {
-            Instruction::TurnOn { start, end } => lights.turn_on(&start, &end),
-            Instruction::TurnOff { start, end } => lights.turn_off(&start, &end),
-            Instruction::Toggle { start, end } => lights.toggle(&start, &end),
+            Instruction::TurnOn { start, end } => lights.turn_on(start, end),
+            Instruction::TurnOff { start, end } => lights.turn_off(start, end),
+            Instruction::Toggle { start, end } => lights.toggle(start, end),
         }
     }
 
     Some(lights.count_on())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(instructions: &[Instruction]) -> Option<usize> {
     let mut lights = LightsTwo::new();
 
-    generic_solution(input, &mut lights)
+    generic_solution(instructions, &mut lights)
 }
 
 #[cfg(test)]
@@ -221,7 +280,17 @@ mod tests {
     #[case("toggle 0,0 through 999,0", 1_000)]
     #[case("turn on 499,499 through 500,500", 4)]
     fn test_part_one(#[case] input: &str, #[case] expected: usize) {
-        let result = part_one(input);
+        let result = part_one(&parse(input).unwrap());
+        assert_eq!(result, Some(expected));
+    }
+
+    #[rstest]
+    #[case("turn on 0,0 through 999,999", 1_000_000)]
+    #[case("toggle 0,0 through 999,0", 1_000)]
+    #[case("turn on 499,499 through 500,500", 4)]
+    #[case("turn on 0,0 through 9,9\ntoggle 5,5 through 14,14\nturn off 0,0 through 0,9", 140)]
+    fn test_part_one_intervals(#[case] input: &str, #[case] expected: usize) {
+        let result = part_one_intervals(&parse(input).unwrap());
         assert_eq!(result, Some(expected));
     }
 
@@ -229,7 +298,7 @@ mod tests {
     #[case("turn on 0,0 through 0,0", 1)]
     #[case("toggle 0,0 through 999,999", 2_000_000)]
     fn test_part_two(#[case] input: &str, #[case] expected: usize) {
-        let result = part_two(input);
+        let result = part_two(&parse(input).unwrap());
         assert_eq!(result, Some(expected));
     }
 }
@@ -1,20 +1,21 @@
 use std::{
     collections::HashMap,
+    num::ParseIntError,
     ops::{BitAnd, BitOr, Shl, Shr},
 };
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2015, 7, parse);
 
-#[derive(Debug, Eq, PartialEq)]
-enum ValueProvider<'a> {
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ValueProvider<'a> {
     Id(Identifier<'a>),
     Constant(u16),
 }
 
 type Identifier<'a> = &'a str;
 
-#[derive(Debug, Eq, PartialEq)]
-enum CircuitElement<'a> {
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CircuitElement<'a> {
     Signal {
         source: ValueProvider<'a>,
         output: Identifier<'a>,
@@ -45,7 +46,7 @@ enum CircuitElement<'a> {
     },
 }
 
-fn parse_valueprovider(input: &str) -> ValueProvider {
+fn parse_valueprovider(input: &str) -> ValueProvider<'_> {
     if let Ok(c) = input.parse::<u16>() {
         ValueProvider::Constant(c)
     } else {
@@ -53,8 +54,17 @@ fn parse_valueprovider(input: &str) -> ValueProvider {
     }
 }
 
-fn parse_line(input: &str) -> CircuitElement {
-    let (before, after) = input.split_once(" -> ").expect("contains arrow");
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum ParseError {
+    MissingArrow,
+    InvalidShift(ParseIntError),
+    UnknownOperation(String),
+    InvalidStructure(String),
+}
+
+fn parse_line(input: &str) -> Result<CircuitElement<'_>, ParseError> {
+    let (before, after) = input.split_once(" -> ").ok_or(ParseError::MissingArrow)?;
 
     let dest = after;
 
@@ -73,38 +83,38 @@ fn parse_line(input: &str) -> CircuitElement {
             },
             "LSHIFT" => CircuitElement::LeftShift {
                 source: parse_valueprovider(before_splits[0]),
-                shift: before_splits[2].parse::<u8>().expect("parsable shift"),
+                shift: before_splits[2].parse::<u8>().map_err(ParseError::InvalidShift)?,
                 output: dest,
             },
             "RSHIFT" => CircuitElement::RightShift {
                 source: parse_valueprovider(before_splits[0]),
-                shift: before_splits[2].parse::<u8>().expect("parsable shift"),
+                shift: before_splits[2].parse::<u8>().map_err(ParseError::InvalidShift)?,
                 output: dest,
             },
-            unexpected => panic!("unexpected operation: {}", unexpected),
+            unexpected => return Err(ParseError::UnknownOperation(unexpected.into())),
         };
 
-        return result;
+        return Ok(result);
     }
 
     if before_splits.len() == 2 && before_splits[0] == "NOT" {
-        return CircuitElement::Not {
+        return Ok(CircuitElement::Not {
             source: parse_valueprovider(before_splits[1]),
             output: dest,
-        };
+        });
     }
 
     if before_splits.len() == 1 {
-        return CircuitElement::Signal {
+        return Ok(CircuitElement::Signal {
             source: parse_valueprovider(before_splits[0]),
             output: dest,
-        };
+        });
     }
 
-    panic!("could not parse {input}");
+    Err(ParseError::InvalidStructure(input.into()))
 }
 
-fn parse(input: &str) -> Vec<CircuitElement> {
+fn parse(input: &str) -> Result<Vec<CircuitElement<'_>>, ParseError> {
     input.lines().map(parse_line).collect()
 }
 
@@ -201,9 +211,8 @@ impl<'a> Circuit<'a> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u16> {
-    let elements = parse(input);
-    let circuit = Circuit::new_from_elements(&elements);
+pub fn part_one(elements: &[CircuitElement]) -> Option<u16> {
+    let circuit = Circuit::new_from_elements(elements);
 
     Some(
         *circuit
@@ -213,8 +222,8 @@ pub fn part_one(input: &str) -> Option<u16> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u16> {
-    let mut elements = parse(input);
+pub fn part_two(elements: &[CircuitElement]) -> Option<u16> {
+    let mut elements = elements.to_vec();
     let circuit = Circuit::new_from_elements(&elements);
 
     let a = *circuit
@@ -257,14 +266,14 @@ mod tests {
     #[case("x LSHIFT 2 -> f", CircuitElement::LeftShift{ source: Id("x"), shift: 2, output: "f"})]
     #[case("NOT x -> h", CircuitElement::Not{ source: Id("x"), output: "h"})]
     fn test_parse(#[case] input: &str, #[case] expected: CircuitElement) {
-        let actual = parse_line(input);
+        let actual = parse_line(input).unwrap();
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn test_circuit_example() {
-        let contents = advent_of_code::template::read_file("examples", DAY);
-        let elements = parse(&contents);
+        let contents = advent_of_code::template::read_file("examples", YEAR, DAY);
+        let elements = parse(&contents).unwrap();
         let circuit = Circuit::new_from_elements(&elements);
 
         let result = circuit.0;
@@ -1,4 +1,4 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(2015, 8);
 
 fn unescape_string(input: &str) -> String {
     let input = input.strip_prefix('"').expect("should start with a quote");
@@ -97,7 +97,7 @@ mod tests {
 
     #[test]
     fn test_unescape_string_examples() {
-        let results: Vec<String> = advent_of_code::template::read_file("examples", DAY)
+        let results: Vec<String> = advent_of_code::template::read_file("examples", YEAR, DAY)
             .lines()
             .map(unescape_string)
             .collect();
@@ -106,7 +106,7 @@ mod tests {
 
     #[test]
     fn test_escape_string_examples() {
-        let results: Vec<usize> = advent_of_code::template::read_file("examples", DAY)
+        let results: Vec<usize> = advent_of_code::template::read_file("examples", YEAR, DAY)
             .lines()
             .map(escape_string)
             .map(|s| s.len())
@@ -116,13 +116,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(19));
     }
 }
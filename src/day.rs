@@ -114,6 +114,82 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A selection of days: a single day (`7`), a range (`1-5`), a list (`3,7,9`),
+/// every day (`all`) or the first day that has not been scaffolded yet (`next`).
+/// Ranges and single days can be combined in a list, e.g. `1-3,7`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySet {
+    Days(Vec<Day>),
+    All,
+    Next,
+}
+
+impl DaySet {
+    /// Returns the selected days in ascending order.
+    /// `is_scaffolded` tells whether a day already has a solution, which is needed to resolve `next`.
+    pub fn resolve(&self, is_scaffolded: impl Fn(Day) -> bool) -> Vec<Day> {
+        match self {
+            DaySet::Days(days) => days.clone(),
+            DaySet::All => all_days().collect(),
+            DaySet::Next => all_days()
+                .find(|day| !is_scaffolded(*day))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "all" => return Ok(DaySet::All),
+            "next" => return Ok(DaySet::Next),
+            _ => {}
+        }
+
+        let mut days = vec![];
+
+        for item in s.split(',').map(str::trim) {
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (start.parse(), end.parse()),
+                None => (item.parse(), item.parse()),
+            };
+            let (start, end): (Day, Day) = (
+                start.map_err(|_| DaySetFromStrError)?,
+                end.map_err(|_| DaySetFromStrError)?,
+            );
+
+            if start > end {
+                return Err(DaySetFromStrError);
+            }
+
+            days.extend(all_days().filter(|day| (start..=end).contains(day)));
+        }
+
+        days.sort();
+        days.dedup();
+        Ok(DaySet::Days(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError;
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting a day (7), a range (1-5), a list (3,7,9), `all` or `next`, with days between 1 and 25",
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +210,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +243,29 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_sets() {
+        let days = |set: &str| set.parse::<DaySet>().unwrap().resolve(|_| false);
+
+        assert_eq!(days("7"), vec![Day(7)]);
+        assert_eq!(days("1-3"), vec![Day(1), Day(2), Day(3)]);
+        assert_eq!(days("9,3, 7,3"), vec![Day(3), Day(7), Day(9)]);
+        assert_eq!(days("1-2,5"), vec![Day(1), Day(2), Day(5)]);
+        assert_eq!(days("all").len(), 25);
+        assert_eq!(days("next"), vec![Day(1)]);
+
+        for invalid in ["", "0", "26", "5-3", "1-", "1,,2", "next,1"] {
+            assert!(invalid.parse::<DaySet>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn resolves_next_day() {
+        let next = DaySet::Next;
+        assert_eq!(next.resolve(|day| day <= 4), vec![Day(5)]);
+        assert_eq!(next.resolve(|_| true), Vec::<Day>::new());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -1,64 +1,169 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, scaffold_file};
+use advent_of_code::template::commands::{all, compare, download, examples, read, scaffold, solve};
+use advent_of_code::template::{paths, registry::Solution};
+use advent_of_code::{Day, DaySet, Year};
 use args::{parse, AppArguments};
 
+#[cfg(all(feature = "in_process", not(test)))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            bench::BenchOptions,
+            commands::{all::AllOptions, solve::SolveOptions},
+            get_year,
+            input_source::{self, InputSource},
+            limits::Limits,
+            runner::{parse_part, RunConfig},
+            scaffold_template::{DEFAULT_RETURN_TYPE, DEFAULT_TEMPLATE},
+        },
+        Day, DaySet, Year,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            year: Year,
+            days: DaySet,
+            force: bool,
+            offline: bool,
         },
         Read {
+            year: Year,
             day: Day,
         },
+        Examples {
+            year: Year,
+            days: DaySet,
+            force: bool,
+        },
         Scaffold {
-            day: Day,
-            file: bool,
+            year: Year,
+            days: DaySet,
+            template: String,
+            return_type: String,
+            update: bool,
         },
         Solve {
+            year: Year,
             day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: SolveOptions,
+            in_process: bool,
         },
         All {
-            release: bool,
-            time: bool,
+            year: Year,
+            options: AllOptions,
+            in_process: bool,
+        },
+        Compare {
+            year: Year,
+            threshold: f64,
+            baseline: Option<String>,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut args = input_source::args_from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+        let Some(subcommand) = args.subcommand()? else {
+            eprintln!("No command specified.");
+            process::exit(1);
+        };
+
+        // `--year` defaults to `AOC_YEAR`, see ".cargo/config.toml".
+        let Some(year) = args.opt_value_from_str("--year")?.or_else(get_year) else {
+            eprintln!("No year specified. Pass `--year <year>` or set `AOC_YEAR` in \".cargo/config.toml\".");
+            process::exit(1);
+        };
+
+        let app_args = match subcommand.as_str() {
+            "all" => {
+                let limits = Limits::from_args(&mut args)?;
+                AppArguments::All {
+                    year,
+                    options: AllOptions {
+                        release: args.contains("--release"),
+                        config: RunConfig {
+                            time: args.contains("--time"),
+                            bench: BenchOptions::from_args(&mut args)?,
+                            part: args.opt_value_from_fn("--part", parse_part)?,
+                            max_memory_mb: limits.max_memory_mb,
+                            mem: args.contains("--mem"),
+                            ..RunConfig::default()
+                        },
+                        jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                        verify: args.contains("--verify"),
+                        limits,
+                        fresh: args.contains("--fresh"),
+                    },
+                    in_process: args.contains("--in-process"),
+                }
+            }
+            "compare" => AppArguments::Compare {
+                year,
+                threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
+                baseline: args.opt_value_from_str("--baseline")?,
             },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "download" => AppArguments::Download {
+                year,
+                days: args.free_from_str()?,
+                force: args.contains("--force"),
+                offline: args.contains("--offline"),
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "examples" => AppArguments::Examples {
+                year,
+                days: args.free_from_str()?,
+                force: args.contains("--force"),
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            "read" => AppArguments::Read {
+                year,
                 day: args.free_from_str()?,
-                file: args.contains("--file"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            "scaffold" => AppArguments::Scaffold {
+                year,
+                days: args.free_from_str()?,
+                // `--file` is kept as a shorthand for `--template file`.
+                template: if args.contains("--file") {
+                    "file".into()
+                } else {
+                    args.opt_value_from_str("--template")?
+                        .unwrap_or_else(|| DEFAULT_TEMPLATE.into())
+                },
+                return_type: args
+                    .opt_value_from_str("--return-type")?
+                    .unwrap_or_else(|| DEFAULT_RETURN_TYPE.into()),
+                update: args.contains("--update"),
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "solve" => {
+                let day = args.free_from_str()?;
+                let input = InputSource::from_args(&mut args)?;
+                let limits = Limits::from_args(&mut args)?;
+                AppArguments::Solve {
+                    year,
+                    day,
+                    options: SolveOptions {
+                        release: args.contains("--release"),
+                        config: RunConfig {
+                            time: args.contains("--time"),
+                            bench: BenchOptions::from_args(&mut args)?,
+                            submit: args.opt_value_from_str("--submit")?,
+                            force: args.contains("--force"),
+                            custom_input: !input.is_puzzle(),
+                            part: args.opt_value_from_fn("--part", parse_part)?,
+                            max_memory_mb: limits.max_memory_mb,
+                            mem: args.contains("--mem"),
+                        },
+                        input,
+                        limits,
+                    },
+                    in_process: args.contains("--in-process"),
+                }
             }
-            None => {
-                eprintln!("No command specified.");
+            x => {
+                eprintln!("Unknown command: {x}");
                 process::exit(1);
             }
         };
@@ -72,6 +177,34 @@ mod args {
     }
 }
 
+/// Returns the solutions compiled into this binary if `in_process` is set.
+fn solutions(in_process: bool) -> Option<&'static [Solution]> {
+    if !in_process {
+        return None;
+    }
+
+    #[cfg(all(feature = "in_process", not(test)))]
+    return Some(registry::SOLUTIONS);
+
+    #[allow(unreachable_code)]
+    {
+        eprintln!("Running solutions in-process requires the `in_process` feature. Try enabling it in \"Cargo.toml\".");
+        std::process::exit(1);
+    }
+}
+
+/// Resolves a selection of days, exiting if it is empty.
+fn resolve_days(year: Year, days: &DaySet) -> Vec<Day> {
+    let days = days.resolve(|day| paths::is_scaffolded(year, day));
+
+    if days.is_empty() {
+        eprintln!("All days of {year} have been scaffolded.");
+        std::process::exit(1);
+    }
+
+    days
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -79,16 +212,50 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, file } => if file { scaffold_file::handle(day) } else { scaffold::handle(day)}
+            AppArguments::All {
+                year,
+                options,
+                in_process,
+            } => all::handle(year, &options, solutions(in_process)),
+            AppArguments::Compare {
+                year,
+                threshold,
+                baseline,
+            } => compare::handle(year, threshold, baseline.as_deref()),
+            AppArguments::Download {
+                year,
+                days,
+                force,
+                offline,
+            } => download::handle(year, &resolve_days(year, &days), force, offline),
+            AppArguments::Examples { year, days, force } => {
+                examples::handle(year, &resolve_days(year, &days), force)
+            }
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Scaffold {
+                year,
+                days,
+                template,
+                return_type,
+                update,
+            } => scaffold::handle(
+                year,
+                &resolve_days(year, &days),
+                &template,
+                &return_type,
+                update,
+            ),
             AppArguments::Solve {
+                year,
                 day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+                options,
+                in_process,
+            } => {
+                if let Err(e) = solve::handle(year, day, &options, solutions(in_process)) {
+                    eprintln!("{e}");
+                    std::process::exit(e.exit_code());
+                }
+            }
         },
     };
 }
@@ -0,0 +1,242 @@
+/// Module that manages the expected answers of solutions, stored in `data/{year}/answers.txt`.
+/// Each line holds a day and part followed by the answer, e.g. `01-2 1795`. Lines starting with `#` are ignored.
+/// Line breaks in answers, e.g. of drawings that could not be read, are escaped (see [`escape`]).
+use std::{collections::BTreeMap, fs, io};
+
+use crate::template::paths;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Why a run did not reproduce an expected answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The part produced a different answer.
+    Wrong,
+    /// The part produced no answer, e.g. because it returned [`None`], failed or panicked.
+    Missing,
+}
+
+/// The expected answers, keyed by day and part.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(Day, u8), String>);
+
+impl Answers {
+    /// Loads the answers file. A missing file is treated as an empty list of answers.
+    pub fn load(year: Year) -> Result<Self, Error> {
+        match fs::read_to_string(answers_path(year)) {
+            Ok(s) => parse_content(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(paths::data_dir(year))?;
+        fs::write(answers_path(year), format_content(self))?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        self.0.insert((day, part), answer.trim().to_string());
+    }
+
+    /// Checks an answer against the expected answer.
+    /// Returns [`None`] if there is no expected answer for this day and part.
+    #[must_use]
+    pub fn verify(&self, day: Day, part: u8, answer: &str) -> Option<bool> {
+        self.get(day, part)
+            .map(|expected| expected == answer.trim())
+    }
+
+    /// Checks the answers produced by a run against all expected answers that `is_run` selects.
+    /// Returns the days and parts whose expected answer was not reproduced.
+    #[must_use]
+    pub fn mismatches(
+        &self,
+        produced: &BTreeMap<(Day, u8), String>,
+        is_run: impl Fn(Day, u8) -> bool,
+    ) -> Vec<(Day, u8, Mismatch)> {
+        self.0
+            .keys()
+            .filter(|&&(day, part)| is_run(day, part))
+            .filter_map(|&(day, part)| match produced.get(&(day, part)) {
+                None => Some((day, part, Mismatch::Missing)),
+                Some(answer) if self.verify(day, part, answer) == Some(false) => {
+                    Some((day, part, Mismatch::Wrong))
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+}
+
+fn answers_path(year: Year) -> String {
+    format!("{}/answers.txt", paths::data_dir(year))
+}
+
+fn parse_line(line: &str) -> Result<((Day, u8), String), Error> {
+    let err = || Error::Parser(format!("malformed answer line: {line}"));
+
+    let (key, answer) = line
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(err)?;
+    let (day, part) = key.split_once('-').ok_or_else(err)?;
+
+    let day: Day = day.parse().map_err(|_| err())?;
+    let part: u8 = part.parse().map_err(|_| err())?;
+
+    Ok(((day, part), unescape(answer.trim())))
+}
+
+fn parse_content(s: &str) -> Result<Answers, Error> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(parse_line)
+        .collect::<Result<_, _>>()
+        .map(Answers)
+}
+
+fn format_content(answers: &Answers) -> String {
+    answers
+        .0
+        .iter()
+        .map(|((day, part), answer)| format!("{day}-{part} {}\n", escape(answer)))
+        .collect()
+}
+
+/// Escapes line breaks, tabs and backslashes, so that an answer fits into a single line or field of a data file.
+#[must_use]
+pub fn escape(answer: &str) -> String {
+    let mut out = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Reverts [`escape`]. Unknown escape sequences are kept as they are.
+#[must_use]
+pub fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_content, parse_content, Answers, Mismatch};
+    use crate::day;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn parses_answers() {
+        let answers =
+            parse_content("# comment\n01-1 280\n\n01-2  1797 \n07-1 some text\n").unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("280"));
+        assert_eq!(answers.get(day!(1), 2), Some("1797"));
+        assert_eq!(answers.get(day!(7), 1), Some("some text"));
+        assert_eq!(answers.get(day!(7), 2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_malformed_line() {
+        parse_content("01 280").unwrap();
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 1, "42");
+        assert_eq!(answers.verify(day!(2), 1, "42"), Some(true));
+        assert_eq!(answers.verify(day!(2), 1, "41"), Some(false));
+        assert_eq!(answers.verify(day!(2), 2, "42"), None);
+    }
+
+    #[test]
+    fn reports_wrong_and_missing_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "1");
+        answers.set(day!(1), 2, "2");
+        answers.set(day!(2), 1, "3");
+        answers.set(day!(2), 2, "4");
+
+        let produced = BTreeMap::from([
+            ((day!(1), 1), "1".to_string()),
+            ((day!(1), 2), "5".to_string()),
+            ((day!(3), 1), "6".to_string()),
+        ]);
+
+        assert_eq!(
+            answers.mismatches(&produced, |_, _| true),
+            vec![
+                (day!(1), 2, Mismatch::Wrong),
+                (day!(2), 1, Mismatch::Missing),
+                (day!(2), 2, Mismatch::Missing),
+            ]
+        );
+        assert_eq!(
+            answers.mismatches(&produced, |_, part| part == 1),
+            vec![(day!(2), 1, Mismatch::Missing)]
+        );
+    }
+
+    #[test]
+    fn roundtrips_multi_line_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(8), 2, "#..#\n#..#\n####\t\\");
+        let s = format_content(&answers);
+        assert_eq!(s, "08-2 #..#\\n#..#\\n####\\t\\\\\n");
+        assert_eq!(parse_content(&s).unwrap(), answers);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(12), 2, "42");
+        answers.set(day!(3), 1, "7");
+        let s = format_content(&answers);
+        assert_eq!(s, "03-1 7\n12-2 42\n");
+        assert_eq!(parse_content(&s).unwrap(), answers);
+    }
+}
@@ -0,0 +1,104 @@
+/// Access to the advent of code website.
+/// Commands talk to the website through the [`AocClient`] trait, which is implemented by the "aoc-cli" wrapper
+/// and by the built-in HTTP client. The environment variable `AOC_CLIENT` selects the implementation.
+use std::{env, fmt::Display, io};
+
+use crate::template::aoc_cli::{self, AocCli, AocCommandError};
+#[cfg(feature = "native")]
+use crate::template::aoc_http::HttpClient;
+use crate::{Day, Year};
+
+pub const CLIENT_ENV: &str = "AOC_CLIENT";
+
+#[derive(Debug)]
+pub enum Error {
+    Cli(AocCommandError),
+    /// `AOC_CLIENT` names a client that does not exist.
+    UnknownClient(String),
+    /// `AOC_CLIENT` selects the built-in client, but the `native` feature is disabled.
+    NativeDisabled,
+    /// No session cookie was found for the built-in client.
+    MissingSession,
+    /// The website did not accept the session cookie.
+    Unauthorized,
+    /// The puzzle does not exist or is not unlocked yet.
+    NotFound,
+    /// The website responded with an unexpected status code.
+    Status(u16),
+    /// The request could not be sent or the response could not be received.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::Cli(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Cli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it, or set {CLIENT_ENV}=native to use the built-in client."
+            ),
+            Error::Cli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::UnknownClient(name) => write!(
+                f,
+                "unknown {CLIENT_ENV} \"{name}\", expecting \"aoc-cli\" or \"native\"."
+            ),
+            Error::NativeDisabled => write!(
+                f,
+                "the built-in client is not compiled in. Enable the \"native\" feature in Cargo.toml to use it."
+            ),
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create an \".adventofcode.session\" file in your home directory."
+            ),
+            Error::Unauthorized => write!(
+                f,
+                "the session cookie was rejected by advent of code. It might have expired."
+            ),
+            Error::NotFound => write!(f, "the puzzle does not exist or is not unlocked yet."),
+            Error::Status(code) => write!(f, "advent of code responded with status {code}."),
+            Error::Transport(e) => write!(f, "request to advent of code failed: {e}"),
+            Error::IO(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+pub trait AocClient {
+    /// Downloads the input and puzzle description of a day to `data/{year}/inputs` and `data/{year}/puzzles`.
+    fn download(&self, year: Year, day: Day) -> Result<(), Error>;
+
+    /// Prints the puzzle description of a day.
+    fn read(&self, year: Year, day: Day) -> Result<(), Error>;
+
+    /// Submits an answer and returns the response of advent of code as text.
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, Error>;
+
+    /// A short description of the client, used in console output.
+    fn name(&self) -> &'static str;
+}
+
+/// Returns the client selected by `AOC_CLIENT`: `aoc-cli` (the default) or `native`.
+pub fn from_env() -> Result<Box<dyn AocClient>, Error> {
+    match env::var(CLIENT_ENV).as_deref() {
+        #[cfg(feature = "native")]
+        Ok("native") => Ok(Box::new(HttpClient::from_env()?)),
+        #[cfg(not(feature = "native"))]
+        Ok("native") => Err(Error::NativeDisabled),
+        Ok("aoc-cli") | Err(_) => {
+            aoc_cli::check()?;
+            Ok(Box::new(AocCli))
+        }
+        Ok(other) => Err(Error::UnknownClient(other.into())),
+    }
+}
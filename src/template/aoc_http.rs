@@ -0,0 +1,326 @@
+/// Built-in HTTP client for the advent of code website.
+/// Authenticates with the session cookie from `AOC_SESSION` or, like aoc-cli, from an `.adventofcode.session` file.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::aoc_client::{AocClient, Error};
+use crate::template::{html, paths};
+use crate::{Day, Year};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Overrides [`BASE_URL`], e.g. to point the client at a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+static SESSION_FILE: &str = ".adventofcode.session";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+pub struct HttpClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl HttpClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .redirects(0)
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, Error> {
+        let session = read_session()?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn get_input(&self, year: Year, day: Day) -> Result<String, Error> {
+        self.get(year, &format!("day/{}/input", day.into_inner()))
+    }
+
+    /// Fetches the puzzle description of a day, converted to markdown.
+    pub fn get_puzzle(&self, year: Year, day: Day) -> Result<String, Error> {
+        let page = self.get(year, &format!("day/{}", day.into_inner()))?;
+        Ok(html::inner_html(&page, "article")
+            .into_iter()
+            .map(html::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Posts an answer and returns the text of the response.
+    pub fn post_answer(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, Error> {
+        let response = self
+            .request("POST", year, &format!("day/{}/answer", day.into_inner()))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let page = read_response(response)?;
+
+        Ok(html::inner_html(&page, "article")
+            .first()
+            .map_or_else(|| html::to_text(&page), |article| html::to_text(article)))
+    }
+
+    fn request(&self, method: &str, year: Year, path: &str) -> ureq::Request {
+        self.agent
+            .request(method, &format!("{}/{year}/{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn get(&self, year: Year, path: &str) -> Result<String, Error> {
+        read_response(self.request("GET", year, path).call())
+    }
+}
+
+impl AocClient for HttpClient {
+    fn download(&self, year: Year, day: Day) -> Result<(), Error> {
+        let input = self.get_input(year, day)?;
+        let puzzle = self.get_puzzle(year, day)?;
+
+        let input_path = paths::input_path(year, day);
+        let puzzle_path = paths::puzzle_path(year, day);
+        write_file(&input_path, &input)?;
+        write_file(&puzzle_path, &puzzle)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    fn read(&self, year: Year, day: Day) -> Result<(), Error> {
+        println!("{}", self.get_puzzle(year, day)?);
+        Ok(())
+    }
+
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, Error> {
+        self.post_answer(year, day, part, answer)
+    }
+
+    fn name(&self) -> &'static str {
+        "the built-in client"
+    }
+}
+
+/// Reads the session cookie from `AOC_SESSION`, `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+pub fn read_session() -> Result<String, Error> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    session_paths()
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or(Error::MissingSession)
+}
+
+fn session_paths() -> Vec<PathBuf> {
+    let Some(home) = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) else {
+        return vec![];
+    };
+    let home = PathBuf::from(home);
+    let config = env::var_os("XDG_CONFIG_HOME").map_or_else(|| home.join(".config"), PathBuf::from);
+
+    vec![
+        home.join(SESSION_FILE),
+        config.join(SESSION_FILE.trim_start_matches('.')),
+    ]
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, Error> {
+    match response {
+        Ok(response) if response.status() >= 300 => Err(Error::Status(response.status())),
+        Ok(response) => Ok(response.into_string()?),
+        // advent of code answers requests with a missing or expired session with 400 or 500.
+        Err(ureq::Error::Status(400 | 401 | 403 | 500, _)) => Err(Error::Unauthorized),
+        Err(ureq::Error::Status(404, _)) => Err(Error::NotFound),
+        Err(ureq::Error::Status(code, _)) => Err(Error::Status(code)),
+        Err(ureq::Error::Transport(e)) => Err(Error::Transport(e.to_string())),
+    }
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::HttpClient;
+    use crate::template::aoc_client::Error;
+    use crate::template::submissions::Verdict;
+    use crate::{day, year};
+
+    /// A recorded request: request line, headers and body.
+    type Recorded = (String, Vec<String>, String);
+
+    /// Serves a single recorded response on a local port and returns the request it received.
+    fn serve(status: &str, body: &'static str) -> (String, JoinHandle<Recorded>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut headers = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_string());
+            }
+
+            let length = headers
+                .iter()
+                .find_map(|h| {
+                    h.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|l| l.trim().parse().unwrap())
+                })
+                .unwrap_or(0);
+            let mut body_buf = vec![0; length];
+            reader.read_exact(&mut body_buf).unwrap();
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            (
+                request_line.trim().to_string(),
+                headers,
+                String::from_utf8(body_buf).unwrap(),
+            )
+        });
+
+        (url, handle)
+    }
+
+    const PUZZLE_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 1 - Advent of Code 2015</title></head><body>
+<header><h1 class="title-global"><a href="/">Advent of Code</a></h1></header>
+<main>
+<article class="day-desc"><h2>--- Day 1: Not Quite Lisp ---</h2><p>Santa is trying to deliver presents in a large apartment building.</p>
+<p>An opening parenthesis, <code>(</code>, means he should go up one floor.</p>
+</article>
+<p>Your puzzle answer was <code>280</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now, given the same instructions, find the <em>position</em> of the first character.</p>
+</article>
+</main>
+</body></html>"#;
+
+    const ANSWER_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 1 - Advent of Code 2015</title></head><body>
+<main>
+<article><p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href="/2015/day/1">[Return to Day 1]</a></p></article>
+</main>
+</body></html>"#;
+
+    #[test]
+    fn downloads_input() {
+        let (url, server) = serve("200 OK", "(()(()(\n");
+        let client = HttpClient::new(&url, "secret");
+
+        assert_eq!(client.get_input(year!(2015), day!(1)).unwrap(), "(()(()(\n");
+
+        let (request_line, headers, _) = server.join().unwrap();
+        assert_eq!(request_line, "GET /2015/day/1/input HTTP/1.1");
+        assert!(headers.iter().any(|h| h == "Cookie: session=secret"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (url, server) = serve("200 OK", PUZZLE_PAGE);
+        let client = HttpClient::new(&url, "secret");
+
+        assert_eq!(
+            client.get_puzzle(year!(2015), day!(1)).unwrap(),
+            "## --- Day 1: Not Quite Lisp ---
+
+Santa is trying to deliver presents in a large apartment building.
+
+An opening parenthesis, `(`, means he should go up one floor.
+
+## --- Part Two ---
+
+Now, given the same instructions, find the *position* of the first character.
+"
+        );
+        assert_eq!(server.join().unwrap().0, "GET /2015/day/1 HTTP/1.1");
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, server) = serve("200 OK", ANSWER_PAGE);
+        let client = HttpClient::new(&url, "secret");
+
+        let response = client.post_answer(year!(2015), day!(1), 2, "42").unwrap();
+        assert!(response.starts_with("That's not the right answer; your answer is too low."));
+        assert_eq!(Verdict::from_output(&response), Verdict::TooLow);
+
+        let (request_line, _, body) = server.join().unwrap();
+        assert_eq!(request_line, "POST /2015/day/1/answer HTTP/1.1");
+        assert_eq!(body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn maps_status_codes() {
+        let (url, server) = serve("404 Not Found", "404 Not Found");
+        let client = HttpClient::new(&url, "secret");
+        assert!(matches!(
+            client.get_input(year!(2015), day!(25)),
+            Err(Error::NotFound)
+        ));
+        server.join().unwrap();
+
+        let (url, server) = serve(
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        let client = HttpClient::new(&url, "expired");
+        assert!(matches!(
+            client.get_input(year!(2015), day!(1)),
+            Err(Error::Unauthorized)
+        ));
+        server.join().unwrap();
+    }
+}
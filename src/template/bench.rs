@@ -0,0 +1,222 @@
+/// Benchmark settings and the statistics computed from benchmark samples.
+use std::time::Duration;
+
+/// Default time spent sampling a solution part.
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+const MIN_SAMPLES: u128 = 10;
+const MAX_SAMPLES: u128 = 10_000;
+const MAX_WARMUP: u128 = 100;
+
+/// Benchmark settings, passed as `--warmup <runs>`, `--samples <count>` and `--bench-time <ms>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Number of untimed runs before sampling starts.
+    pub warmup: Option<u32>,
+    /// Fixed number of samples. Takes precedence over the time budget.
+    pub samples: Option<u32>,
+    /// Approximate time spent sampling, in milliseconds.
+    pub budget_ms: Option<u64>,
+}
+
+impl BenchOptions {
+    /// Reads the benchmark settings from the arguments of the current process.
+    pub fn from_env() -> Result<Self, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_env();
+        Self::from_args(&mut args)
+    }
+
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            warmup: args.opt_value_from_str("--warmup")?,
+            samples: args.opt_value_from_str("--samples")?,
+            budget_ms: args.opt_value_from_str("--bench-time")?,
+        })
+    }
+
+    /// Converts the settings back into arguments that can be forwarded to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(warmup) = self.warmup {
+            args.push("--warmup".into());
+            args.push(warmup.to_string());
+        }
+
+        if let Some(samples) = self.samples {
+            args.push("--samples".into());
+            args.push(samples.to_string());
+        }
+
+        if let Some(budget_ms) = self.budget_ms {
+            args.push("--bench-time".into());
+            args.push(budget_ms.to_string());
+        }
+
+        args
+    }
+
+    fn budget(&self) -> Duration {
+        self.budget_ms.map_or(DEFAULT_BUDGET, Duration::from_millis)
+    }
+
+    /// Number of samples to take for a function whose first execution took `base_time`.
+    /// Without an explicit sample count, this fills the time budget with at least 10 and at most 10.000 samples.
+    #[must_use]
+    pub fn sample_count(&self, base_time: &Duration) -> u128 {
+        match self.samples {
+            Some(samples) => u128::from(samples.max(1)),
+            None => (self.budget().as_nanos() / base_time.as_nanos().max(10))
+                .clamp(MIN_SAMPLES, MAX_SAMPLES),
+        }
+    }
+
+    /// Number of warmup runs for a function whose first execution took `base_time`.
+    /// Without an explicit count, warmup takes up about a tenth of the time budget.
+    #[must_use]
+    pub fn warmup_count(&self, base_time: &Duration) -> u128 {
+        match self.warmup {
+            Some(warmup) => u128::from(warmup),
+            None => (self.budget().as_nanos() / 10 / base_time.as_nanos().max(10)).min(MAX_WARMUP),
+        }
+    }
+}
+
+/// Summary statistics of a set of benchmark samples.
+///
+/// Outliers are detected with Tukey's fences (1.5 × the interquartile range).
+/// `min`, `median` and `p95` describe all samples, `mean` and `stddev` only the samples that are not outliers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub outliers: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Statistics for a single, unrepeated execution.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            outliers: 0,
+            min: duration,
+            median: duration,
+            p95: duration,
+            mean: duration,
+            stddev: Duration::ZERO,
+        }
+    }
+
+    /// Computes statistics from a list of samples. Returns [`None`] if `samples` is empty.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let (low, high) = (q1 - fence, q3 + fence);
+
+        let inliers: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|&x| x >= low && x <= high)
+            .collect();
+
+        let mean = inliers.iter().sum::<f64>() / inliers.len() as f64;
+        let variance = if inliers.len() > 1 {
+            inliers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (inliers.len() - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            samples: sorted.len(),
+            outliers: sorted.len() - inliers.len(),
+            min: nanos(sorted[0]),
+            median: nanos(percentile(&sorted, 0.5)),
+            p95: nanos(percentile(&sorted, 0.95)),
+            mean: nanos(mean),
+            stddev: nanos(variance.sqrt()),
+        })
+    }
+}
+
+/// Linear interpolation between the closest ranks of a sorted, non-empty list.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos(value: f64) -> Duration {
+    Duration::from_nanos(value.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchOptions, Stats};
+    use std::time::Duration;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&x| Duration::from_millis(x)).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_micros(4800));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.stddev, Duration::from_nanos(1_581_139));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&millis(&[10, 10, 11, 10, 9, 10, 12, 11, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.mean, Duration::from_micros(10_375));
+    }
+
+    #[test]
+    fn sample_count() {
+        let defaults = BenchOptions::default();
+        assert_eq!(defaults.sample_count(&Duration::from_millis(10)), 100);
+        assert_eq!(defaults.sample_count(&Duration::from_secs(2)), 10);
+        assert_eq!(defaults.sample_count(&Duration::from_nanos(1)), 10_000);
+
+        let fixed = BenchOptions {
+            samples: Some(42),
+            ..BenchOptions::default()
+        };
+        assert_eq!(fixed.sample_count(&Duration::from_millis(10)), 42);
+
+        let budget = BenchOptions {
+            budget_ms: Some(100),
+            ..BenchOptions::default()
+        };
+        assert_eq!(budget.sample_count(&Duration::from_millis(1)), 100);
+        assert_eq!(budget.warmup_count(&Duration::from_millis(1)), 10);
+    }
+}
@@ -1,52 +1,522 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::template::{
+    answers::{Answers, Mismatch},
+    history,
+    input_source::InputSource,
+    limits::Limits,
+    memory, paths,
     readme_benchmarks::{self, Timings},
+    registry::{self, Solution},
+    report::{self, PartReport},
+    run_cache::{self, Fingerprinter},
+    runner::{self, RunConfig},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, Year};
+
+/// Options of the `all` command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllOptions {
+    pub release: bool,
+    /// Settings passed on to every solution. Answers are never submitted.
+    pub config: RunConfig,
+    /// Number of days to run in parallel.
+    pub jobs: usize,
+    /// Exit with a non-zero status if a known answer is not reproduced.
+    pub verify: bool,
+    pub limits: Limits,
+    /// Run every day, ignoring the cached results of unchanged days.
+    pub fresh: bool,
+}
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(year: Year, options: &AllOptions, solutions: Option<&[Solution]>) {
+    let started = Instant::now();
     let mut timings: Vec<Timings> = vec![];
+    let mut answered: BTreeMap<(Day, u8), String> = BTreeMap::new();
+    let mut statuses: Vec<(Day, DayStatus)> = vec![];
+
+    let AllOptions {
+        release: is_release,
+        config,
+        jobs,
+        verify: is_verify,
+        limits,
+        fresh: is_fresh,
+    } = *options;
+    let (is_timed, part, mem) = (config.time, config.part, config.mem);
+
+    if limits.is_set() && solutions.is_some() {
+        eprintln!("Ignoring `--timeout` and `--max-memory` for an in-process run, solutions can only be limited when run as separate binaries.");
+    }
+
+    if mem && solutions.is_some() && !memory::IS_INSTALLED {
+        eprintln!("Not counting allocations, running solutions in-process requires building with the `mem` feature.");
+    }
+
+    let jobs = if jobs > 1 && is_timed {
+        eprintln!("Ignoring `--jobs` for a timed run, days are run sequentially to not perturb benchmarks.");
+        1
+    } else if jobs > 1 && solutions.is_some() {
+        eprintln!("Ignoring `--jobs` for an in-process run, days are run sequentially.");
+        1
+    } else {
+        jobs.max(1)
+    };
+
+    let profile = if is_release { "release" } else { "debug" };
+    let in_process = solutions.is_some();
+    let fingerprints = fingerprint_days(year, &format!("{profile} {in_process} {config:?}"));
+    let mut cached = if is_fresh {
+        BTreeMap::new()
+    } else {
+        load_cached(year, &fingerprints)
+    };
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+    let mut on_day_finished = |day: Day, run: DayRun| {
+        let DayRun {
+            reports,
+            status,
+            is_cached,
+            ..
+        } = run;
+
+        answered.extend(
+            reports
+                .iter()
+                .filter_map(|r| Some(((r.day, r.part), r.answer.clone()?))),
+        );
+
+        if !is_cached && status == DayStatus::Solved {
+            if let Some(fingerprint) = fingerprints.get(&day) {
+                if let Err(e) = run_cache::store(year, day, fingerprint, &reports) {
+                    eprintln!("Failed to cache the results of day {day}: {e:?}");
+                }
+            }
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        match &status {
+            DayStatus::Solved => {}
+            DayStatus::Missing => println!("Not solved."),
+            // errors returned by a part have already been printed by the solution.
+            DayStatus::Failed(Failure::Part(_)) => {}
+            DayStatus::Failed(failure) => println!("✖ Solution {failure}."),
+            DayStatus::TimedOut(timeout) => println!("✖ Timed out after {timeout:?}."),
+        }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if !reports.is_empty() || matches!(status, DayStatus::TimedOut(_)) {
+            let mut day_timings = child_commands::timings_from_reports(&reports, day);
+            day_timings.is_cached = is_cached;
+            if matches!(status, DayStatus::TimedOut(_)) {
+                child_commands::mark_timed_out(&mut day_timings, &config);
+            }
+            timings.push(day_timings);
+        }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        statuses.push((day, status));
+    };
+
+    // build all binaries upfront, so that parallel invocations do not wait on cargo's build lock.
+    if solutions.is_none() && jobs > 1 && cached.len() < all_days().count() {
+        if let Err(e) = child_commands::build_solutions(&config, is_release) {
+            eprintln!("Failed to build solutions: {e:?}");
         }
-    });
+    }
+
+    if jobs > 1 {
+        run_parallel(
+            year,
+            is_release,
+            jobs,
+            &config,
+            limits.timeout,
+            cached,
+            &mut on_day_finished,
+        );
+    } else {
+        all_days().for_each(|day| {
+            print_day_header(day);
+
+            let run = if let Some(run) = cached.remove(&day) {
+                print!("{}", run.output);
+                run
+            } else {
+                match solutions {
+                    Some(solutions) => match registry::find(solutions, year, day)
+                        .map(|s| s.run(&config, &InputSource::Puzzle))
+                    {
+                        Some(Ok(reports)) => DayRun::from_reports(reports),
+                        Some(Err(registry::Error::Panicked)) => {
+                            DayRun::new(DayStatus::Failed(Failure::Panic))
+                        }
+                        Some(Err(e)) => DayRun::new(DayStatus::Failed(Failure::Run(e.to_string()))),
+                        None => DayRun::new(DayStatus::Missing),
+                    },
+                    None => child_commands::run_solution(
+                        year,
+                        day,
+                        &config,
+                        limits.timeout,
+                        is_release,
+                        false,
+                    ),
+                }
+            };
+
+            on_day_finished(day, run);
+        });
+    }
+
+    print_summary(&statuses, started);
+
+    if is_verify {
+        verify(year, &answered, &config);
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+
+        let entries = history::entries_for_run(&timings, &history::current_commit(), profile);
+        if let Err(e) = history::append(year, &entries) {
+            eprintln!("Failed to record benchmark history: {e:?}");
+        }
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+            // a partial run would blank out the other part's timings in the table.
+            if let Some(part) = part {
+                println!("Not updating README benchmarks, only part {part} was run.");
+            } else {
+                match readme_benchmarks::update(year, timings, total_millis) {
+                    Ok(()) => println!("Successfully updated README with benchmarks."),
+                    Err(_) => {
+                        eprintln!("Failed to update readme with benchmarks.");
+                    }
+                }
+            }
+        }
+    }
+
+    if statuses.iter().any(|(_, status)| status.is_failure()) {
+        process::exit(1);
+    }
+}
+
+/// The outcome of running the solution of a day.
+pub struct DayRun {
+    pub reports: Vec<PartReport>,
+    /// Output of the build and the solution, if it was captured.
+    pub output: String,
+    pub status: DayStatus,
+    /// The results were taken from the cache instead of running the solution.
+    pub is_cached: bool,
+}
+
+impl DayRun {
+    fn new(status: DayStatus) -> Self {
+        Self {
+            reports: vec![],
+            output: String::new(),
+            status,
+            is_cached: false,
+        }
+    }
+
+    fn from_reports(reports: Vec<PartReport>) -> Self {
+        Self {
+            status: DayStatus::from_reports(&reports),
+            reports,
+            ..Self::new(DayStatus::Missing)
+        }
+    }
+}
+
+/// Fingerprints the scaffolded days, see [`run_cache`]. Days that can't be fingerprinted are not cached.
+fn fingerprint_days(year: Year, settings: &str) -> BTreeMap<Day, String> {
+    let fingerprinter = match Fingerprinter::new(settings) {
+        Ok(fingerprinter) => fingerprinter,
+        Err(e) => {
+            eprintln!("Failed to fingerprint sources, not caching results: {e:?}");
+            return BTreeMap::new();
+        }
+    };
+
+    all_days()
+        .filter(|&day| paths::is_scaffolded(year, day))
+        .filter_map(|day| match fingerprinter.fingerprint(year, day) {
+            Ok(fingerprint) => Some((day, fingerprint)),
+            Err(e) => {
+                eprintln!("Failed to fingerprint day {day}: {e:?}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Loads the cached results of all days whose fingerprint did not change.
+/// Cached answers are verified against the current expected answers.
+fn load_cached(year: Year, fingerprints: &BTreeMap<Day, String>) -> BTreeMap<Day, DayRun> {
+    let answers = Answers::load(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read expected answers: {e:?}");
+        Answers::default()
+    });
+
+    fingerprints
+        .iter()
+        .filter_map(
+            |(&day, fingerprint)| match run_cache::load(year, day, fingerprint) {
+                Ok(reports) => reports.map(|reports| (day, reports)),
+                Err(e) => {
+                    eprintln!("Failed to read cached results of day {day}: {e:?}");
+                    None
                 }
+            },
+        )
+        .map(|(day, mut reports)| {
+            for report in &mut reports {
+                report.correct = report
+                    .answer
+                    .as_ref()
+                    .and_then(|answer| answers.verify(day, report.part, answer));
+            }
+
+            let run = DayRun {
+                output: format_cached(&reports, &answers),
+                is_cached: true,
+                ..DayRun::from_reports(reports)
+            };
+            (day, run)
+        })
+        .collect()
+}
+
+fn format_cached(reports: &[PartReport], answers: &Answers) -> String {
+    reports
+        .iter()
+        .map(|r| {
+            let duration =
+                Duration::from_nanos(u64::try_from(r.duration_nanos).unwrap_or(u64::MAX));
+            let mem = r.mem.map_or_else(String::new, |mem| format!(" [{mem}]"));
+            let cached = format!(" {ANSI_ITALIC}(cached, {duration:.1?}){ANSI_RESET}{mem}\n");
+
+            match (r.part, &r.answer) {
+                (0, _) => format!("Parse:{cached}"),
+                (part, Some(answer)) if answer.contains('\n') => {
+                    format!("Part {part}: ▼{cached}{answer}\n")
+                }
+                (part, Some(answer)) => format!(
+                    "Part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}{}{cached}",
+                    runner::format_verification(r.correct, answers.get(r.day, part))
+                ),
+                (part, None) => format!("Part {part}: ✖{cached}"),
+            }
+        })
+        .collect()
+}
+
+/// Why the solution of a day failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The solution binary did not compile.
+    Build,
+    Panic,
+    /// The solution binary exited with a non-zero status, or was terminated by a signal if `None`.
+    Exit(Option<i32>),
+    /// A part returned an error.
+    Part(u8),
+    /// The solution could not be run, e.g. because its input is missing.
+    Run(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Build => write!(f, "failed to build"),
+            Failure::Panic => write!(f, "panicked"),
+            Failure::Exit(Some(code)) => write!(f, "exited with status {code}"),
+            Failure::Exit(None) => write!(f, "was terminated by a signal"),
+            Failure::Part(part) => write!(f, "returned an error for part {part}"),
+            Failure::Run(e) => write!(f, "could not be run: {e}"),
+        }
+    }
+}
+
+/// How the run of a single day ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayStatus {
+    /// At least one part returned an answer.
+    Solved,
+    /// The day has not been scaffolded yet, or none of its parts returned an answer.
+    Missing,
+    Failed(Failure),
+    TimedOut(Duration),
+}
+
+impl DayStatus {
+    /// Status of a day whose solution ran to completion.
+    #[must_use]
+    pub fn from_reports(reports: &[PartReport]) -> Self {
+        if let Some(report) = reports.iter().find(|r| r.error.is_some()) {
+            DayStatus::Failed(Failure::Part(report.part))
+        } else if reports.iter().any(|r| r.success) {
+            DayStatus::Solved
+        } else {
+            DayStatus::Missing
+        }
+    }
+
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        matches!(self, DayStatus::Failed(_) | DayStatus::TimedOut(_))
+    }
+}
+
+fn print_summary(statuses: &[(Day, DayStatus)], started: Instant) {
+    let count = |f: fn(&DayStatus) -> bool| statuses.iter().filter(|(_, s)| f(s)).count();
+
+    println!(
+        "\n{ANSI_BOLD}Summary:{ANSI_RESET} {} solved, {} failed, {} timed out, {} missing {ANSI_ITALIC}({:.2?}){ANSI_RESET}",
+        count(|s| *s == DayStatus::Solved),
+        count(|s| matches!(s, DayStatus::Failed(_))),
+        count(|s| matches!(s, DayStatus::TimedOut(_))),
+        count(|s| *s == DayStatus::Missing),
+        started.elapsed()
+    );
+
+    for (day, status) in statuses {
+        match status {
+            DayStatus::Failed(failure) => eprintln!("Day {day}: solution {failure}."),
+            DayStatus::TimedOut(timeout) => eprintln!("Day {day}: timed out after {timeout:?}."),
+            DayStatus::Solved | DayStatus::Missing => {}
+        }
+    }
+}
+
+/// Exits with a non-zero status if any expected answer was not reproduced by the run,
+/// i.e. a part produced a different answer or no answer at all.
+fn verify(year: Year, answered: &BTreeMap<(Day, u8), String>, config: &RunConfig) {
+    let answers = Answers::load(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read expected answers: {e:?}");
+        process::exit(1);
+    });
+
+    let mismatches = answers.mismatches(answered, |_, part| config.runs_part(part));
+
+    if mismatches.is_empty() {
+        println!("🎄 All known answers are correct.");
+        return;
+    }
+
+    for (day, part, mismatch) in &mismatches {
+        match mismatch {
+            Mismatch::Wrong => {
+                eprintln!("Day {day} Part {part}: answer does not match the expected answer.");
+            }
+            Mismatch::Missing => {
+                eprintln!(
+                    "Day {day} Part {part}: no answer, expected {}.",
+                    answers.get(*day, *part).unwrap_or_default()
+                );
             }
         }
     }
+
+    eprintln!(
+        "{} known answer(s) were not reproduced, see \"{}/answers.txt\".",
+        mismatches.len(),
+        paths::data_dir(year)
+    );
+    process::exit(1);
+}
+
+fn print_day_header(day: Day) {
+    if day > 1 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Runs the solutions of all days on `jobs` threads, each spawning one solution binary at a time.
+/// Output is buffered per day and printed in day order as soon as all previous days have finished.
+fn run_parallel(
+    year: Year,
+    is_release: bool,
+    jobs: usize,
+    config: &RunConfig,
+    timeout: Option<Duration>,
+    cached: BTreeMap<Day, DayRun>,
+    on_day_finished: &mut impl FnMut(Day, DayRun),
+) {
+    let days: Vec<Day> = all_days().filter(|day| !cached.contains_key(day)).collect();
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let (days, next) = (&days, &next);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+
+                let run =
+                    child_commands::run_solution(year, day, config, timeout, is_release, true);
+
+                if sender.send((day, run)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(sender);
+
+        // cached days can be printed as soon as all previous days have finished.
+        let mut pending = cached;
+        let mut order = all_days().peekable();
+
+        let mut print_pending = |pending: &mut BTreeMap<Day, DayRun>| {
+            while let Some(&day) = order.peek() {
+                let Some(run) = pending.remove(&day) else {
+                    break;
+                };
+
+                print_day_header(day);
+                print!("{}", run.output);
+                on_day_finished(day, run);
+
+                order.next();
+            }
+        };
+
+        print_pending(&mut pending);
+
+        for (day, run) in receiver {
+            pending.insert(day, run);
+            print_pending(&mut pending);
+        }
+    });
 }
 
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
-    Parser(String),
+    Report(report::Error),
     IO(io::Error),
 }
 
@@ -56,103 +526,254 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<report::Error> for Error {
+    fn from(e: report::Error) -> Self {
+        Error::Report(e)
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{}", paths::bin_path(year, day))
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the timings they report.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use super::{get_path_for_bin, DayRun, DayStatus, Error, Failure};
+    use crate::template::{
+        commands::cargo_command,
+        limits::{self, Exit},
+        memory::{self, MemStats},
+        paths,
+        readme_benchmarks::Timings,
+        report::{self, PartReport, REPORT_FILE_ENV},
+        runner::RunConfig,
+    };
+    use crate::{Day, Year};
     use std::{
-        io::{BufRead, BufReader},
+        env, fs, io,
         path::Path,
-        process::{Command, Stdio},
-        thread,
+        process::{self, Stdio},
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Cell shown in place of the timing of a part that exceeded the timeout.
+    const TIMED_OUT: &str = "timed out";
+
+    /// Exit code of a rust process that panicked.
+    const PANIC_EXIT_CODE: i32 = 101;
+
+    /// Build and run the solution bin for a given day and return the records it reported.
+    /// If `capture` is set, the output of the solution is returned instead of forwarded to stdout / stderr.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        config: &RunConfig,
+        timeout: Option<Duration>,
+        is_release: bool,
+        capture: bool,
+    ) -> DayRun {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return DayRun::new(DayStatus::Missing);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // building separately tells build failures apart from panics, which both exit `cargo run` with 101.
+        // it also keeps build times out of the timeout.
+        let mut output = match build_solution(year, day, config, is_release, capture) {
+            Ok((true, output)) => output,
+            Ok((false, output)) => {
+                return DayRun {
+                    output,
+                    ..DayRun::new(DayStatus::Failed(Failure::Build))
+                }
+            }
+            Err(e) => return DayRun::new(DayStatus::Failed(Failure::Run(format!("{e:?}")))),
+        };
 
-        if is_release {
-            args.push("--release");
+        match execute_solution(year, day, config, timeout, is_release, capture) {
+            Ok((reports, exit, run_output)) => {
+                output.push_str(&run_output);
+
+                let status = match exit {
+                    Exit::TimedOut(timeout) => DayStatus::TimedOut(timeout),
+                    Exit::Finished(status) => match status.code() {
+                        Some(0) => DayStatus::from_reports(&reports),
+                        Some(PANIC_EXIT_CODE) => DayStatus::Failed(Failure::Panic),
+                        code => DayStatus::Failed(Failure::Exit(code)),
+                    },
+                };
+
+                DayRun {
+                    reports,
+                    output,
+                    status,
+                    is_cached: false,
+                }
+            }
+            Err(e) => DayRun {
+                output,
+                ..DayRun::new(DayStatus::Failed(Failure::Run(format!("{e:?}"))))
+            },
         }
+    }
+
+    /// Build the solution bin for a given day. Returns whether the build succeeded.
+    fn build_solution(
+        year: Year,
+        day: Day,
+        config: &RunConfig,
+        is_release: bool,
+        capture: bool,
+    ) -> Result<(bool, String), Error> {
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            paths::bin_name(year, day),
+        ];
+
+        args.append(&mut profile_args(config, is_release));
+
+        let (exit, output) = limits::run_child(cargo_command().args(&args), None, capture)?;
+        Ok((
+            matches!(exit, Exit::Finished(status) if status.success()),
+            output,
+        ))
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    fn execute_solution(
+        year: Year,
+        day: Day,
+        config: &RunConfig,
+        timeout: Option<Duration>,
+        is_release: bool,
+        capture: bool,
+    ) -> Result<(Vec<PartReport>, Exit, String), Error> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            paths::bin_name(year, day),
+        ];
+
+        args.append(&mut profile_args(config, is_release));
+        args.push("--".into());
+
+        if config.time {
+            // mirror `--time` flag and benchmark settings to child invocations.
+            args.push("--time".into());
+            args.append(&mut config.bench.to_args());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        if let Some(part) = config.part {
+            args.push("--part".into());
+            args.push(part.to_string());
+        }
+
+        if let Some(mb) = config.max_memory_mb {
+            args.push("--max-memory".into());
+            args.push(mb.to_string());
+        }
+
+        if config.mem {
+            args.push("--mem".into());
+        }
+
+        let report_path =
+            env::temp_dir().join(format!("aoc-report-{}-{year}-{day}.jsonl", process::id()));
+        remove_report(&report_path)?;
+
+        let mut cmd = cargo_command();
+        cmd.args(&args).env(REPORT_FILE_ENV, &report_path);
+
+        let (exit, output) = limits::run_child(&mut cmd, timeout, capture)?;
+
+        let reports = report::read(&report_path)?;
+        remove_report(&report_path)?;
+
+        Ok((reports, exit, output))
+    }
 
-        let mut cmd = Command::new("cargo")
+    /// Build all solution binaries without running them.
+    pub fn build_solutions(config: &RunConfig, is_release: bool) -> Result<(), Error> {
+        let mut args: Vec<String> = vec!["build".into(), "--quiet".into(), "--bins".into()];
+        args.append(&mut profile_args(config, is_release));
+
+        cargo_command()
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        Ok(())
+    }
 
-        let mut output = vec![];
+    /// Cargo arguments that select how solutions are built.
+    /// `--mem` runs enable the `mem` feature, which installs the counting allocator.
+    fn profile_args(config: &RunConfig, is_release: bool) -> Vec<String> {
+        let mut args = vec![];
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        if is_release {
+            args.push("--release".into());
+        }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if config.mem {
+            args.push("--features".into());
+            args.push(memory::FEATURE.into());
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        args
+    }
 
-        Ok(output)
+    fn remove_report(path: &Path) -> Result<(), Error> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
+    pub fn timings_from_reports(reports: &[PartReport], day: Day) -> Timings {
+        let mut timings = Timings {
             day,
+            parse: None,
+            parse_nanos: None,
             part_1: None,
             part_2: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
+            part_1_mem: None,
+            part_2_mem: None,
             total_nanos: 0_f64,
+            is_cached: false,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .filter(|r| r.success && r.day == day)
+            .for_each(|r| {
+                #[allow(clippy::cast_possible_truncation)]
+                let timing_str = format!("{:.1?}", Duration::from_nanos(r.duration_nanos as u64));
+                #[allow(clippy::cast_precision_loss)]
+                let nanos = r.duration_nanos as f64;
+
+                match r.part {
+                    0 => {
+                        timings.parse = Some(timing_str);
+                        timings.parse_nanos = Some(nanos);
+                    }
+                    1 => {
+                        timings.part_1 = Some(timing_str);
+                        timings.part_1_nanos = Some(nanos);
+                        timings.part_1_mem = r.mem.as_ref().map(format_memory);
+                    }
+                    2 => {
+                        timings.part_2 = Some(timing_str);
+                        timings.part_2_nanos = Some(nanos);
+                        timings.part_2_mem = r.mem.as_ref().map(format_memory);
+                    }
+                    _ => return,
                 }
 
                 timings.total_nanos += nanos;
@@ -161,92 +782,123 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Memory cell of the benchmark table, e.g. `1.5 MiB peak, 3 allocs`.
+    fn format_memory(mem: &MemStats) -> String {
+        format!(
+            "{} peak, {} allocs",
+            memory::format_bytes(mem.peak_bytes),
+            mem.allocations
+        )
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Marks the parts of a day that were run but did not report a timing as timed out.
+    pub fn mark_timed_out(timings: &mut Timings, config: &RunConfig) {
+        for (part, timing) in [(1, &mut timings.part_1), (2, &mut timings.part_2)] {
+            if config.runs_part(part) && timing.is_none() {
+                *timing = Some(TIMED_OUT.into());
+            }
+        }
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{mark_timed_out, timings_from_reports};
+        use crate::template::{
+            commands::all::{DayStatus, Failure},
+            report::PartReport,
+            runner::RunConfig,
+        };
 
         use crate::day;
 
+        fn report(part: u8, duration_nanos: u128, success: bool) -> PartReport {
+            PartReport {
+                day: day!(1),
+                part,
+                answer: success.then(|| "10".into()),
+                duration_nanos,
+                samples: 100,
+                success,
+                correct: None,
+                error: None,
+                mem: None,
+            }
+        }
+
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let res =
+                timings_from_reports(&[report(1, 74, true), report(2, 74_130_000, true)], day!(1));
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_2_nanos, Some(74_130_000_f64));
+            assert_eq!(res.parse, None);
+        }
+
+        #[test]
+        fn test_parse_phase() {
+            let res = timings_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(0, 26, true),
+                    report(1, 74, true),
+                    report(2, 100, true),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 200_f64);
+            assert_eq!(res.parse.unwrap(), "26.0ns");
+            assert_eq!(res.parse_nanos, Some(26_f64));
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn test_single_part() {
+            let res = timings_from_reports(&[report(1, 74, true)], day!(1));
+            assert_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_2_nanos, None);
+        }
+
+        #[test]
+        fn test_timed_out() {
+            let mut res = timings_from_reports(&[report(1, 74, true)], day!(1));
+            mark_timed_out(&mut res, &RunConfig::default());
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "timed out");
+            assert_eq!(res.part_2_nanos, None);
+            assert_eq!(res.total_nanos, 74_f64);
+        }
+
+        #[test]
+        fn test_day_status() {
+            assert_eq!(
+                DayStatus::from_reports(&[report(1, 74, true), report(2, 100, true)]),
+                DayStatus::Solved
+            );
+            assert_eq!(
+                DayStatus::from_reports(&[report(1, 74, true), report(2, 0, false)]),
+                DayStatus::Solved
+            );
+            assert_eq!(
+                DayStatus::from_reports(&[report(1, 0, false), report(2, 0, false)]),
+                DayStatus::Missing
+            );
+            assert_eq!(DayStatus::from_reports(&[]), DayStatus::Missing);
+
+            let mut failed = report(2, 0, false);
+            failed.error = Some("bad".into());
+            assert_eq!(
+                DayStatus::from_reports(&[report(1, 74, true), failed]),
+                DayStatus::Failed(Failure::Part(2))
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            let res =
+                timings_from_reports(&[report(1, 100, false), report(2, 100, false)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
@@ -0,0 +1,58 @@
+use std::process;
+
+use crate::template::{history, ANSI_BOLD, ANSI_RESET};
+use crate::Year;
+
+pub fn handle(year: Year, threshold_percent: f64, baseline_commit: Option<&str>) {
+    let entries = match history::load(year) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e:?}");
+            process::exit(1);
+        }
+    };
+
+    let Some(changes) = history::compare(&entries, baseline_commit) else {
+        eprintln!("No baseline to compare against. Run `cargo time` at least twice to record one.");
+        process::exit(1);
+    };
+
+    let mut regressions = 0;
+
+    for change in &changes {
+        let is_regression = change.is_regression(threshold_percent);
+        if is_regression {
+            regressions += 1;
+        }
+
+        println!(
+            "Day {} {}: {:.1?} -> {:.1?} ({}){}",
+            change.day,
+            if change.part == 0 {
+                "Parse".into()
+            } else {
+                format!("Part {}", change.part)
+            },
+            to_duration(change.baseline_nanos),
+            to_duration(change.current_nanos),
+            change
+                .percent()
+                .map_or_else(|| "n/a".into(), |percent| format!("{percent:+.1}%")),
+            if is_regression { " ✖ slower" } else { "" }
+        );
+    }
+
+    println!("---");
+
+    if regressions > 0 {
+        println!("{ANSI_BOLD}{regressions} part(s) got more than {threshold_percent}% slower.{ANSI_RESET}");
+        process::exit(1);
+    }
+
+    println!("🎄 No part got more than {threshold_percent}% slower.");
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> std::time::Duration {
+    std::time::Duration::from_nanos(nanos as u64)
+}
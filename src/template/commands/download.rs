@@ -1,15 +1,42 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use crate::template::input_cache::{self, InputMeta};
+use crate::template::{aoc_client, paths};
+use crate::{Day, Year};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(year: Year, days: &[Day], force: bool, offline: bool) {
+    for &day in days {
+        download_day(year, day, force, offline);
+    }
+}
+
+fn download_day(year: Year, day: Day, force: bool, offline: bool) {
+    if !force && input_cache::is_cached(year, day) {
+        println!(
+            "🎄 Input already exists at \"{}\". Append `--force` to download it again.",
+            paths::input_path(year, day)
+        );
+        return;
+    }
+
+    if offline {
+        eprintln!(
+            "Not downloading day {day} of {year}: `--offline` is set and the input is not cached."
+        );
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let result = aoc_client::from_env().and_then(|client| client.download(year, day));
+
+    if let Err(e) = result {
+        eprintln!("failed to download day {day} of {year}: {e}");
         process::exit(1);
     };
+
+    let meta = fs::read_to_string(paths::input_path(year, day))
+        .map_err(input_cache::Error::from)
+        .and_then(|input| InputMeta::for_content(year, &input).save(day));
+
+    if let Err(e) = meta {
+        eprintln!("Failed to write input metadata: {e:?}");
+    }
 }
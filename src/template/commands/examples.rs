@@ -0,0 +1,57 @@
+use std::{fs, process};
+
+use crate::template::examples::PuzzleExamples;
+use crate::template::paths;
+use crate::{Day, Year};
+
+pub fn handle(year: Year, days: &[Day], force: bool) {
+    for &day in days {
+        extract_day(year, day, force);
+    }
+}
+
+fn extract_day(year: Year, day: Day, force: bool) {
+    let examples = match PuzzleExamples::load(year, day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle \"{}\": {e}. Run `cargo download {day} --year {year}` first.",
+                paths::puzzle_path(year, day)
+            );
+            process::exit(1);
+        }
+    };
+
+    if examples.example.is_none() {
+        println!("No example found for day {day}.");
+    }
+
+    match examples.write(year, day, force) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote example to \"{path}\"");
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to write examples: {e}");
+            process::exit(1);
+        }
+    }
+
+    for (part, answer) in examples.answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            println!("Part {}: expected example answer {answer}", part + 1);
+        }
+    }
+
+    let module_path = paths::bin_path(year, day);
+    if let Ok(module) = fs::read_to_string(&module_path) {
+        let filled = examples.fill_expectations(&module);
+        if filled != module {
+            match fs::write(&module_path, filled) {
+                Ok(()) => println!("Filled in expected answers in \"{module_path}\""),
+                Err(e) => eprintln!("Failed to update module file: {e}"),
+            }
+        }
+    }
+}
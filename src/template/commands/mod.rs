@@ -1,6 +1,41 @@
 pub mod all;
+pub mod compare;
 pub mod download;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
-pub mod scaffold_file;
 pub mod solve;
+
+use std::{env, process::Command};
+
+/// Variables that cargo sets for the binary it runs. A nested cargo invocation that inherits them
+/// rebuilds dependencies whose build scripts track them, e.g. `CARGO_MANIFEST_DIR`.
+const INHERITED_CARGO_VARS: [&str; 7] = [
+    "CARGO_MANIFEST_DIR",
+    "CARGO_MANIFEST_PATH",
+    "CARGO_PRIMARY_PACKAGE",
+    "CARGO_CRATE_NAME",
+    "CARGO_BIN_NAME",
+    "CARGO_RUSTC_CURRENT_DIR",
+    "CARGO_PKG_",
+];
+
+/// Creates a `cargo` command for building or running solution binaries, without the variables
+/// that the outer cargo invocation set for this binary.
+pub fn cargo_command() -> Command {
+    let mut cmd = Command::new("cargo");
+
+    for (key, _) in env::vars_os() {
+        let is_inherited = key.to_str().is_some_and(|key| {
+            INHERITED_CARGO_VARS
+                .iter()
+                .any(|var| key == *var || (var.ends_with('_') && key.starts_with(var)))
+        });
+
+        if is_inherited {
+            cmd.env_remove(key);
+        }
+    }
+
+    cmd
+}
@@ -1,98 +1,240 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::examples::PuzzleExamples;
+use crate::template::paths;
+use crate::template::scaffold_template::{self, Context, Template};
+use crate::{Day, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// Creates an empty file unless it exists already. Returns whether the file was created.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    if Path::new(path).exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    safe_create_file(path).map(|_| true)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+/// Scaffolds a list of days from a template. When scaffolding more than one day, days that already exist are skipped.
+/// With `update`, existing solutions are kept and only tests of the template that they are missing are added.
+pub fn handle(year: Year, days: &[Day], template: &str, return_type: &str, update: bool) {
+    let template = match Template::load(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    for &day in days {
+        if !update && days.len() > 1 && paths::is_scaffolded(year, day) {
+            println!(
+                "Skipping day {day}, \"{}\" already exists.",
+                paths::bin_path(year, day)
+            );
+            continue;
+        }
+
+        scaffold_day(&template, year, day, return_type, update);
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn scaffold_day(template: &Template, year: Year, day: Day, return_type: &str, update: bool) {
+    let input_path = paths::input_path(year, day);
+    let example_path = paths::example_path(year, day);
+    let module_path = paths::bin_path(year, day);
 
-    use rstest::rstest;
+    let context = Context {
+        year,
+        day,
+        return_type,
+    };
+
+    // pre-fill examples and expected answers if the puzzle has been downloaded already.
+    let examples = PuzzleExamples::load(year, day).unwrap_or_default();
+
+    let module = match render_module(template, &context, &examples) {
+        Ok(module) => module,
+        Err(e) => {
+            eprintln!("Failed to render template \"{}\": {e}", template.name);
+            process::exit(1);
+        }
+    };
 
-    #[rstest]
-    #[case("input", 42)]
-    fn test_part_one(#[case] input: &str, #[case] expected: u32) {
-        let result = part_one(input);
-        assert_eq!(result, Some(expected));
+    if update && paths::is_scaffolded(year, day) {
+        update_module(&module_path, &module, &examples);
+    } else {
+        let mut file = match safe_create_file(&module_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                eprintln!("Module file \"{module_path}\" already exists. Pass `--update` to add missing tests to it.");
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to create module file: {e}");
+                process::exit(1);
+            }
+        };
+
+        match file.write_all(module.as_bytes()) {
+            Ok(()) => {
+                println!("Created module file \"{}\"", &module_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to write module contents: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    match create_file(&input_path) {
+        Ok(true) => {
+            println!("Created empty input file \"{}\"", &input_path);
+        }
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to create input file: {e}");
+            process::exit(1);
+        }
     }
 
-    #[rstest]
-    #[case("input", 42)]
-    fn test_part_two(#[case] input: &str, #[case] expected: u32) {
-        let result = part_two(input);
-        assert_eq!(result, Some(expected));
+    if template.uses_examples() {
+        match create_file(&example_path) {
+            Ok(true) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+
+        match examples.write(year, day, false) {
+            Ok(written) => {
+                for path in written {
+                    println!("Wrote example from puzzle description to \"{path}\"");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write examples: {e}");
+                process::exit(1);
+            }
+        }
     }
-}
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+    println!("---");
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Renders the module of a day with the examples and expected answers of the puzzle description filled in.
+fn render_module(
+    template: &Template,
+    context: &Context,
+    examples: &PuzzleExamples,
+) -> Result<String, scaffold_template::Error> {
+    template
+        .render(context)
+        .map(|module| examples.fill_expectations(&module))
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    // let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Adds the tests of the rendered template that are missing in `existing` and fills in expected answers.
+/// Returns the merged module and the names of the added tests.
+fn merge_module(
+    existing: &str,
+    rendered: &str,
+    examples: &PuzzleExamples,
+) -> (String, Vec<String>) {
+    let (merged, added) = scaffold_template::merge_tests(existing, rendered);
+    (examples.fill_expectations(&merged), added)
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+/// Updates an existing solution with [`merge_module`] and writes it back if anything changed.
+fn update_module(module_path: &str, rendered: &str, examples: &PuzzleExamples) {
+    let existing = match fs::read_to_string(module_path) {
+        Ok(existing) => existing,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to read module file: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let (merged, added) = merge_module(&existing, rendered, examples);
+
+    if merged == existing {
+        println!("Module file \"{module_path}\" is up to date.");
+        return;
+    }
+
+    match fs::write(module_path, merged) {
+        Ok(()) if added.is_empty() => {
+            println!("Filled in expected answers in \"{module_path}\"");
+        }
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Added {} to \"{module_path}\"", added.join(", "));
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{merge_module, render_module};
+    use crate::template::examples::PuzzleExamples;
+    use crate::template::scaffold_template::{Context, Template, DEFAULT_TEMPLATE};
+    use crate::{day, year};
+
+    fn context() -> Context<'static> {
+        Context {
+            year: year!(2015),
+            day: day!(1),
+            return_type: "u32",
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    }
+
+    fn examples() -> PuzzleExamples {
+        PuzzleExamples {
+            example: Some("(())".into()),
+            example_part_two: None,
+            answers: [Some("0".into()), None],
         }
     }
 
-    // match create_file(&example_path) {
-    //     Ok(_) => {
-    //         println!("Created empty example file \"{}\"", &example_path);
-    //     }
-    //     Err(e) => {
-    //         eprintln!("Failed to create example file: {e}");
-    //         process::exit(1);
-    //     }
-    // }
+    #[test]
+    fn fills_default_template_from_puzzle() {
+        let template = Template::load(DEFAULT_TEMPLATE).unwrap();
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+        let module = render_module(&template, &context(), &examples()).unwrap();
+        assert!(module.contains("    #[case(\"(())\", 0)]\n    fn test_part_one("));
+        assert!(module.contains("    #[case(\"input\", 42)]\n    fn test_part_two("));
+    }
+
+    #[test]
+    fn updates_default_template_placeholders() {
+        let template = Template::load(DEFAULT_TEMPLATE).unwrap();
+        let existing = template
+            .render(&context())
+            .unwrap()
+            .replace("    None\n}", "    Some(0)\n}");
+        let rendered = render_module(&template, &context(), &examples()).unwrap();
+
+        let (merged, added) = merge_module(&existing, &rendered, &examples());
+        assert!(added.is_empty());
+        assert!(merged.contains("    Some(0)\n}"));
+        assert!(merged.contains("    #[case(\"(())\", 0)]\n    fn test_part_one("));
+        assert!(merged.contains("    #[case(\"input\", 42)]\n    fn test_part_two("));
+    }
 }
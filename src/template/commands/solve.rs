@@ -1,31 +1,196 @@
-use std::process::{Command, Stdio};
+use std::fmt::Display;
+use std::io;
+use std::process::Stdio;
+use std::time::Duration;
 
-use crate::Day;
+use crate::template::commands::cargo_command;
+use crate::template::input_source::InputSource;
+use crate::template::limits::{self, Exit, Limits};
+use crate::template::memory;
+use crate::template::paths;
+use crate::template::registry::{self, Solution};
+use crate::template::runner::RunConfig;
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[derive(Debug)]
+pub enum Error {
+    /// The day has no solution yet.
+    NotScaffolded(Year, Day),
+    /// The solution binary did not compile.
+    Build,
+    /// The solution exited with a non-zero status, or was terminated by a signal if `None`.
+    Exit(Option<i32>),
+    TimedOut(Duration),
+    /// Running the solution in-process failed.
+    Run(registry::Error),
+    IO(io::Error),
+}
 
-    if release {
-        cmd_args.push("--release".to_string());
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotScaffolded(year, day) => write!(
+                f,
+                "Day {day} of {year} has not been scaffolded yet. Run `cargo scaffold {day} --year {year}` to create it."
+            ),
+            Error::Build => write!(f, "The solution failed to build."),
+            Error::Exit(Some(code)) => write!(f, "The solution exited with status {code}."),
+            Error::Exit(None) => write!(f, "The solution was terminated by a signal."),
+            Error::TimedOut(timeout) => write!(f, "The solution timed out after {timeout:?}."),
+            Error::Run(e) => write!(f, "Failed to run the solution: {e}"),
+            Error::IO(e) => write!(f, "Failed to run cargo: {e}"),
+        }
     }
+}
+
+impl Error {
+    /// Exit code for the `solve` command, the exit code of the solution if it failed.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Exit(Some(code)) => *code,
+            _ => 1,
+        }
+    }
+}
+
+/// Options of the `solve` command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveOptions {
+    pub release: bool,
+    /// Settings of the run, forwarded to the solution binary.
+    pub config: RunConfig,
+    pub input: InputSource,
+    pub limits: Limits,
+}
 
+pub fn handle(
+    year: Year,
+    day: Day,
+    options: &SolveOptions,
+    solutions: Option<&[Solution]>,
+) -> Result<(), Error> {
+    let SolveOptions {
+        release,
+        config,
+        input,
+        limits,
+    } = options;
+    let (release, limits, mem) = (*release, *limits, config.mem);
+
+    if let Some(solutions) = solutions {
+        if limits.is_set() {
+            eprintln!("Ignoring `--timeout` and `--max-memory` for an in-process run, solutions can only be limited when run as separate binaries.");
+        }
+
+        if mem && !memory::IS_INSTALLED {
+            eprintln!("Not counting allocations, running solutions in-process requires building with the `mem` feature.");
+        }
+
+        let config = RunConfig {
+            max_memory_mb: None,
+            ..*config
+        };
+        return run_in_process(year, day, solutions, &config, input);
+    }
+
+    if !paths::is_scaffolded(year, day) {
+        return Err(Error::NotScaffolded(year, day));
+    }
+
+    let mut cmd_args = cargo_args("run", year, day, release, mem);
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = config.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if config.force {
+        cmd_args.push("--force".to_string());
+    }
+
+    if config.time {
         cmd_args.push("--time".to_string());
+        cmd_args.append(&mut config.bench.to_args());
     }
 
-    let mut cmd = Command::new("cargo")
+    cmd_args.append(&mut input.to_args());
+
+    if let Some(part) = config.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    cmd_args.append(&mut limits.to_args());
+
+    if mem {
+        cmd_args.push("--mem".to_string());
+    }
+
+    // build upfront, so that build failures are reported as such and don't count towards the timeout.
+    build_solution(year, day, release, mem)?;
+
+    match limits::run_child(cargo_command().args(&cmd_args), limits.timeout, false)? {
+        (Exit::TimedOut(timeout), _) => Err(Error::TimedOut(timeout)),
+        (Exit::Finished(status), _) if status.success() => Ok(()),
+        (Exit::Finished(status), _) => Err(Error::Exit(status.code())),
+    }
+}
+
+/// Arguments of a cargo command for the solution bin of a day.
+/// `--mem` runs enable the `mem` feature, which installs the counting allocator.
+fn cargo_args(command: &str, year: Year, day: Day, release: bool, mem: bool) -> Vec<String> {
+    let mut cmd_args = vec![
+        command.to_string(),
+        "--bin".to_string(),
+        paths::bin_name(year, day),
+    ];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    if mem {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(memory::FEATURE.to_string());
+    }
+
+    cmd_args
+}
+
+fn build_solution(year: Year, day: Day, release: bool, mem: bool) -> Result<(), Error> {
+    let mut cmd_args = cargo_args("build", year, day, release, mem);
+    cmd_args.push("--quiet".to_string());
+
+    let status = cargo_command()
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Build)
+    }
+}
+
+fn run_in_process(
+    year: Year,
+    day: Day,
+    solutions: &[Solution],
+    config: &RunConfig,
+    input: &InputSource,
+) -> Result<(), Error> {
+    let solution = registry::find(solutions, year, day).ok_or(Error::NotScaffolded(year, day))?;
 
-    cmd.wait().unwrap();
+    solution.run(config, input).map_err(Error::Run)?;
+    Ok(())
 }
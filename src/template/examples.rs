@@ -0,0 +1,388 @@
+/// Module that extracts example inputs and their expected answers from downloaded puzzle descriptions.
+/// Examples are the first code block of each part, expected answers the last highlighted value (e.g. "*`42`*") of each part.
+use std::{fs, io};
+
+use crate::template::{html, paths, scaffold_template};
+use crate::{Day, Year};
+
+static PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// The placeholder case of the tests scaffolded by the default template.
+static PLACEHOLDER_CASE: &str = r#"#[case("input", 42)]"#;
+
+/// Example inputs and expected answers found in a puzzle description.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub example: Option<String>,
+    /// The example of part two, if it differs from the example of part one.
+    pub example_part_two: Option<String>,
+    pub answers: [Option<String>; 2],
+}
+
+impl PuzzleExamples {
+    /// Reads the downloaded puzzle description of a day and extracts its examples.
+    pub fn load(year: Year, day: Day) -> io::Result<Self> {
+        fs::read_to_string(paths::puzzle_path(year, day)).map(|s| extract(&s))
+    }
+
+    /// Writes the examples to `data/{year}/examples/{day}.txt` and `{day}-2.txt`.
+    /// Existing examples are only overwritten if they are empty or `force` is set. Returns the paths that were written.
+    pub fn write(&self, year: Year, day: Day, force: bool) -> io::Result<Vec<String>> {
+        let examples_dir = format!("{}/examples", paths::data_dir(year));
+        fs::create_dir_all(&examples_dir)?;
+
+        let files = [
+            (paths::example_path(year, day), &self.example),
+            (
+                format!("{examples_dir}/{day}-2.txt"),
+                &self.example_part_two,
+            ),
+        ];
+
+        let mut written = vec![];
+
+        for (path, example) in files {
+            let Some(example) = example else {
+                continue;
+            };
+
+            let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+            if force || is_empty {
+                fs::write(&path, example)?;
+                written.push(path);
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Fills in the expected results of the tests of a scaffolded module. For the default template, the placeholder
+    /// case is replaced by the example and its answer. For `scaffold --file`, the assertion is filled in.
+    /// Only tests that still contain the scaffolded placeholder are changed.
+    /// Only numeric answers are filled in, as scaffolded parts return numbers.
+    #[must_use]
+    pub fn fill_expectations(&self, module: &str) -> String {
+        let mut module = module.to_string();
+
+        if self.example_part_two.is_some() {
+            module = replace_in_fn(
+                &module,
+                "test_part_two",
+                r#"read_file("examples", YEAR, DAY)"#,
+                r#"read_file_part("examples", YEAR, DAY, 2)"#,
+            );
+        }
+
+        let examples = [
+            self.example.as_ref(),
+            self.example_part_two.as_ref().or(self.example.as_ref()),
+        ];
+
+        for ((test, answer), example) in ["test_part_one", "test_part_two"]
+            .iter()
+            .zip(&self.answers)
+            .zip(examples)
+        {
+            let Some(answer) = answer.as_ref().filter(|a| a.parse::<i64>().is_ok()) else {
+                continue;
+            };
+
+            module = replace_in_fn(
+                &module,
+                test,
+                "assert_eq!(result, None);",
+                &format!("assert_eq!(result, Some({answer}));"),
+            );
+
+            if let Some(example) = example {
+                module = replace_in_attributes(
+                    &module,
+                    test,
+                    PLACEHOLDER_CASE,
+                    &format!("#[case({example:?}, {answer})]"),
+                );
+            }
+        }
+
+        module
+    }
+}
+
+/// Extracts examples from a puzzle description in markdown. HTML descriptions are converted first.
+#[must_use]
+pub fn extract(puzzle: &str) -> PuzzleExamples {
+    let markdown = if puzzle.contains("<pre>") || puzzle.contains("<article") {
+        html::to_markdown(puzzle)
+    } else {
+        puzzle.to_string()
+    };
+
+    let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+        Some(pos) => (&markdown[..pos], Some(&markdown[pos..])),
+        None => (markdown.as_str(), None),
+    };
+
+    let (blocks_one, text_one) = split_code_blocks(part_one);
+    let (blocks_two, text_two) = part_two.map(split_code_blocks).unwrap_or_default();
+
+    let example = blocks_one.into_iter().next();
+    let example_part_two = blocks_two
+        .into_iter()
+        .next()
+        .filter(|e| Some(e) != example.as_ref());
+
+    PuzzleExamples {
+        example,
+        example_part_two,
+        answers: [highlights(&text_one).pop(), highlights(&text_two).pop()],
+    }
+}
+
+/// Splits markdown into the contents of its fenced code blocks and the remaining text.
+fn split_code_blocks(markdown: &str) -> (Vec<String>, String) {
+    let mut blocks = vec![];
+    let mut text = String::new();
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(b) => blocks.push(b),
+                None => block = Some(String::new()),
+            }
+        } else if let Some(b) = block.as_mut() {
+            b.push_str(line);
+            b.push('\n');
+        } else {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    (blocks, text)
+}
+
+/// Finds highlighted inline code, written as "*`42`*" or "`*42*`".
+fn highlights(text: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['*', '`']) {
+        rest = &rest[start..];
+
+        let closing = if rest.starts_with("*`") {
+            "`*"
+        } else if rest.starts_with("`*") {
+            "*`"
+        } else {
+            rest = &rest[1..];
+            continue;
+        };
+
+        let content = &rest[2..];
+        match content.find(closing) {
+            Some(end) if end > 0 && !content[..end].contains('\n') => {
+                found.push(content[..end].to_string());
+                rest = &content[end + 2..];
+            }
+            _ => rest = content,
+        }
+    }
+
+    found
+}
+
+/// Replaces the first occurrence of `from` inside the body of function `name`.
+fn replace_in_fn(module: &str, name: &str, from: &str, to: &str) -> String {
+    let Some(start) = module.find(&format!("fn {name}(")) else {
+        return module.to_string();
+    };
+    let Some(end) = module[start..]
+        .find('{')
+        .and_then(|pos| scaffold_template::matching_brace(module, start + pos))
+    else {
+        return module.to_string();
+    };
+
+    match module[start..end].find(from) {
+        Some(pos) => replace_at(module, start + pos, from, to),
+        None => module.to_string(),
+    }
+}
+
+/// Replaces the last occurrence of `from` in the attributes of function `name`.
+fn replace_in_attributes(module: &str, name: &str, from: &str, to: &str) -> String {
+    let Some(start) = module.find(&format!("fn {name}(")) else {
+        return module.to_string();
+    };
+
+    match module[..start].rfind(from) {
+        // anything but attributes between the match and the function means it belongs to another item.
+        Some(pos)
+            if module[pos + from.len()..start]
+                .lines()
+                .all(|l| l.trim().is_empty() || l.trim().starts_with("#[")) =>
+        {
+            replace_at(module, pos, from, to)
+        }
+        _ => module.to_string(),
+    }
+}
+
+fn replace_at(module: &str, pos: usize, from: &str, to: &str) -> String {
+    format!("{}{to}{}", &module[..pos], &module[pos + from.len()..])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, highlights, PuzzleExamples};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+The newly-improved calibration document consists of lines of text, for example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these four lines are *`12`* and *`38`*. Adding these together produces *`142`*.
+
+## --- Part Two ---
+
+It looks like some of the digits are actually spelled out with letters, for example:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces `*281*`.
+";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let examples = extract(PUZZLE);
+        assert_eq!(examples.example.as_deref(), Some("1abc2\npqr3stu8vwx\n"));
+        assert_eq!(
+            examples.example_part_two.as_deref(),
+            Some("two1nine\neightwothree\n")
+        );
+        assert_eq!(examples.answers, [Some("142".into()), Some("281".into())]);
+    }
+
+    #[test]
+    fn extracts_part_one_only() {
+        let part_one = &PUZZLE[..PUZZLE.find("## --- Part Two").unwrap()];
+        let examples = extract(part_one);
+        assert_eq!(examples.example_part_two, None);
+        assert_eq!(examples.answers, [Some("142".into()), None]);
+    }
+
+    #[test]
+    fn extracts_from_html() {
+        let html = "<article><h2>--- Day 1 ---</h2><pre><code>1\n2\n</code></pre><p>Total: <code><em>3</em></code>.</p></article>";
+        let examples = extract(html);
+        assert_eq!(examples.example.as_deref(), Some("1\n2\n"));
+        assert_eq!(examples.answers, [Some("3".into()), None]);
+    }
+
+    #[test]
+    fn finds_highlights() {
+        assert_eq!(
+            highlights("a *`1`* b `*x y*` c `code` *em* *`2`*"),
+            vec!["1", "x y", "2"]
+        );
+    }
+
+    #[test]
+    fn fills_expectations() {
+        let module = r#"
+    fn test_part_one() {
+        // the result of fn part_one
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+"#;
+        let examples = PuzzleExamples {
+            example: Some("1".into()),
+            example_part_two: Some("2".into()),
+            answers: [Some("142".into()), Some("abc".into())],
+        };
+
+        let filled = examples.fill_expectations(module);
+        assert_eq!(
+            filled,
+            r#"
+    fn test_part_one() {
+        // the result of fn part_one
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Some(142));
+    }
+
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_part("examples", YEAR, DAY, 2));
+        assert_eq!(result, None);
+    }
+"#
+        );
+
+        let examples = PuzzleExamples {
+            answers: [Some("1".into()), Some("2".into())],
+            ..examples
+        };
+        assert!(examples
+            .fill_expectations(&filled)
+            .ends_with("assert_eq!(result, Some(2));\n    }\n"));
+    }
+
+    #[test]
+    fn fills_default_template_cases() {
+        let module = r#"
+    #[rstest]
+    #[case("input", 42)]
+    fn test_part_one(#[case] input: &str, #[case] expected: u32) {
+        let result = part_one(input);
+        assert_eq!(result, Some(expected));
+    }
+
+    #[rstest]
+    #[case("input", 42)]
+    fn test_part_two(#[case] input: &str, #[case] expected: u32) {
+        let result = part_two(input);
+        assert_eq!(result, Some(expected));
+    }
+"#;
+        let examples = PuzzleExamples {
+            example: Some("1\n\"2\"\n".into()),
+            example_part_two: None,
+            answers: [Some("142".into()), Some("281".into())],
+        };
+
+        let filled = examples.fill_expectations(module);
+        assert_eq!(
+            filled,
+            r#"
+    #[rstest]
+    #[case("1\n\"2\"\n", 142)]
+    fn test_part_one(#[case] input: &str, #[case] expected: u32) {
+        let result = part_one(input);
+        assert_eq!(result, Some(expected));
+    }
+
+    #[rstest]
+    #[case("1\n\"2\"\n", 281)]
+    fn test_part_two(#[case] input: &str, #[case] expected: u32) {
+        let result = part_two(input);
+        assert_eq!(result, Some(expected));
+    }
+"#
+        );
+        assert_eq!(examples.fill_expectations(&filled), filled);
+    }
+}
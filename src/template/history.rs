@@ -0,0 +1,318 @@
+/// Module that persists the timings of benchmarked runs and detects regressions between them.
+/// Every timed run of `cargo all` appends one line per day and part to `data/{year}/benchmarks.tsv`.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{paths, readme_benchmarks::Timings};
+use crate::{Day, Year};
+static HEADER: &str = "# timestamp\tcommit\tprofile\tday\tpart\tnanos";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The timing of one part of a day, as recorded by one run. Part `0` is the parse phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Start of the run in milliseconds since the unix epoch. Identifies the run.
+    pub timestamp: u128,
+    pub commit: String,
+    pub profile: String,
+    pub day: Day,
+    pub part: u8,
+    pub nanos: f64,
+}
+
+/// A part whose timing changed between the baseline and the current run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl Change {
+    /// Relative change in percent, positive if the part got slower.
+    /// Returns [`None`] if the baseline took no measurable time, as there is nothing to compare against.
+    #[must_use]
+    pub fn percent(&self) -> Option<f64> {
+        if self.baseline_nanos == 0.0 {
+            return None;
+        }
+
+        Some((self.current_nanos / self.baseline_nanos - 1.0) * 100.0)
+    }
+
+    #[must_use]
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.percent()
+            .is_some_and(|percent| percent > threshold_percent)
+    }
+}
+
+/// Converts the timings of a run into history entries.
+/// Cached timings are skipped, as they were not measured by this run.
+#[must_use]
+pub fn entries_for_run(timings: &[Timings], commit: &str, profile: &str) -> Vec<Entry> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+
+    timings
+        .iter()
+        .filter(|t| !t.is_cached)
+        .flat_map(|t| {
+            [(0, t.parse_nanos), (1, t.part_1_nanos), (2, t.part_2_nanos)]
+                .into_iter()
+                .filter_map(|(part, nanos)| {
+                    Some(Entry {
+                        timestamp,
+                        commit: commit.into(),
+                        profile: profile.into(),
+                        day: t.day,
+                        part,
+                        nanos: nanos?,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Short hash of the checked-out commit, suffixed with `-dirty` if the sources have uncommitted changes.
+#[must_use]
+pub fn current_commit() -> String {
+    let Ok(output) = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+    else {
+        return "unknown".into();
+    };
+
+    if !output.status.success() {
+        return "unknown".into();
+    }
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["diff", "--quiet", "HEAD", "--", "src", "Cargo.toml"])
+        .status()
+        .is_ok_and(|status| !status.success());
+
+    if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    }
+}
+
+fn format_entry(entry: &Entry) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        entry.timestamp, entry.commit, entry.profile, entry.day, entry.part, entry.nanos
+    )
+}
+
+fn parse_entry(line: &str) -> Result<Entry, Error> {
+    let err = || Error::Parser(format!("malformed history line: {line}"));
+
+    let [timestamp, commit, profile, day, part, nanos] = line.split('\t').collect::<Vec<_>>()[..]
+    else {
+        return Err(err());
+    };
+
+    Ok(Entry {
+        timestamp: timestamp.parse().map_err(|_| err())?,
+        commit: commit.into(),
+        profile: profile.into(),
+        day: day.parse().map_err(|_| err())?,
+        part: part.parse().map_err(|_| err())?,
+        nanos: nanos.parse().map_err(|_| err())?,
+    })
+}
+
+fn parse_content(s: &str) -> Result<Vec<Entry>, Error> {
+    s.lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(parse_entry)
+        .collect()
+}
+
+fn history_path(year: Year) -> String {
+    format!("{}/benchmarks.tsv", paths::data_dir(year))
+}
+
+/// Appends the entries of a run to the history file, creating it if necessary.
+pub fn append(year: Year, entries: &[Entry]) -> Result<(), Error> {
+    let path = history_path(year);
+    let is_new = fs::metadata(&path).is_err();
+
+    fs::create_dir_all(paths::data_dir(year))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+    if is_new {
+        writeln!(file, "{HEADER}")?;
+    }
+
+    for entry in entries {
+        writeln!(file, "{}", format_entry(entry))?;
+    }
+
+    Ok(())
+}
+
+/// Loads all entries from the history file. A missing file is treated as an empty history.
+pub fn load(year: Year) -> Result<Vec<Entry>, Error> {
+    match fs::read_to_string(history_path(year)) {
+        Ok(s) => parse_content(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Compares the latest run against a baseline run with the same profile.
+/// The baseline is the latest earlier run, or the latest earlier run of `baseline_commit` if given.
+/// Returns [`None`] if there is no baseline to compare against.
+#[must_use]
+pub fn compare(entries: &[Entry], baseline_commit: Option<&str>) -> Option<Vec<Change>> {
+    let current_run = entries.iter().map(|e| e.timestamp).max()?;
+    let profile = &entries.iter().find(|e| e.timestamp == current_run)?.profile;
+
+    let baseline_run = entries
+        .iter()
+        .filter(|e| e.timestamp < current_run && &e.profile == profile)
+        .filter(|e| baseline_commit.is_none_or(|c| e.commit.starts_with(c)))
+        .map(|e| e.timestamp)
+        .max()?;
+
+    let baseline: HashMap<(Day, u8), f64> = entries
+        .iter()
+        .filter(|e| e.timestamp == baseline_run)
+        .map(|e| ((e.day, e.part), e.nanos))
+        .collect();
+
+    let mut changes: Vec<Change> = entries
+        .iter()
+        .filter(|e| e.timestamp == current_run)
+        .filter_map(|e| {
+            Some(Change {
+                day: e.day,
+                part: e.part,
+                baseline_nanos: *baseline.get(&(e.day, e.part))?,
+                current_nanos: e.nanos,
+            })
+        })
+        .collect();
+
+    changes.sort_by_key(|c| (c.day, c.part));
+    Some(changes)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, entries_for_run, format_entry, parse_content, Entry};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+
+    fn entry(timestamp: u128, commit: &str, day: u8, part: u8, nanos: f64) -> Entry {
+        Entry {
+            timestamp,
+            commit: commit.into(),
+            profile: "release".into(),
+            day: crate::Day::new(day).unwrap(),
+            part,
+            nanos,
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let e = entry(1_700_000_000_000, "abc1234", 7, 2, 1234.5);
+        let s = format!("# header\n{}\n", format_entry(&e));
+        assert_eq!(parse_content(&s).unwrap(), vec![e]);
+    }
+
+    #[test]
+    fn skips_cached_timings() {
+        let timings = |day, is_cached| Timings {
+            day,
+            parse: None,
+            part_1: Some("1.0µs".into()),
+            part_2: None,
+            parse_nanos: None,
+            part_1_nanos: Some(1000.0),
+            part_2_nanos: None,
+            part_1_mem: None,
+            part_2_mem: None,
+            total_nanos: 1000.0,
+            is_cached,
+        };
+
+        let entries = entries_for_run(
+            &[timings(day!(1), true), timings(day!(2), false)],
+            "abc",
+            "release",
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].day, day!(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_malformed_line() {
+        parse_content("1\tabc\trelease\t01\t1").unwrap();
+    }
+
+    #[test]
+    fn compares_latest_run_against_previous_run() {
+        let entries = vec![
+            entry(1, "aaa", 1, 1, 100.0),
+            entry(1, "aaa", 1, 2, 100.0),
+            entry(2, "bbb", 1, 1, 200.0),
+            entry(2, "bbb", 1, 2, 200.0),
+            entry(3, "ccc", 1, 1, 210.0),
+            entry(3, "ccc", 1, 2, 100.0),
+            entry(3, "ccc", 2, 1, 100.0),
+        ];
+
+        let changes = compare(&entries, None).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].day, day!(1));
+        assert!(!changes[0].is_regression(10.0));
+        assert!(changes[0].is_regression(4.0));
+        assert!((changes[1].percent().unwrap() + 50.0).abs() < 1e-9);
+
+        let changes = compare(&entries, Some("aaa")).unwrap();
+        assert!(changes[0].is_regression(100.0));
+        assert!(!changes[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn skips_zero_baseline() {
+        let entries = vec![entry(1, "aaa", 1, 1, 0.0), entry(2, "bbb", 1, 1, 100.0)];
+
+        let changes = compare(&entries, None).unwrap();
+        assert_eq!(changes[0].percent(), None);
+        assert!(!changes[0].is_regression(0.0));
+    }
+
+    #[test]
+    fn no_baseline() {
+        assert_eq!(compare(&[entry(1, "aaa", 1, 1, 1.0)], None), None);
+        assert_eq!(compare(&[], None), None);
+    }
+}
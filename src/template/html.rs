@@ -0,0 +1,330 @@
+/// Minimal HTML handling for pages of the advent of code website.
+/// Only the handful of tags used in puzzle descriptions and submission responses are understood.
+use std::fmt::Write;
+
+/// Returns the inner HTML of every `<tag>` element in `html`. Nested elements of the same tag are not supported.
+#[must_use]
+pub fn inner_html<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut out = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // make sure we matched `<article>` or `<article ...>`, not e.g. `<articles>`.
+        if !after.starts_with(['>', ' ', '\t', '\n']) {
+            rest = after;
+            continue;
+        }
+        let Some(content_start) = after.find('>') else {
+            break;
+        };
+        let content = &after[content_start + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        out.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+
+    out
+}
+
+/// Converts HTML to plain text, collapsing whitespace.
+#[must_use]
+pub fn to_text(html: &str) -> String {
+    let text: String = tokenize(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(s) => Some(decode_entities(s)),
+            _ => None,
+        })
+        .collect();
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Converts a puzzle description to markdown.
+#[must_use]
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    // advent of code highlights answers with `<code><em>42</em></code>`, which is written as "*`42`*".
+    let mut em_wraps_code = false;
+    let mut links: Vec<Option<String>> = vec![];
+
+    for token in tokenize(html) {
+        match token {
+            Token::Text(s) if in_pre => out.push_str(&decode_entities(s)),
+            Token::Text(s) => {
+                let text = collapse_whitespace(&decode_entities(s));
+                if out.is_empty() || out.ends_with('\n') {
+                    out.push_str(text.trim_start());
+                } else {
+                    out.push_str(&text);
+                }
+            }
+            Token::Open(_, _) | Token::Close(_) if in_pre && !token.is("pre") => {}
+            Token::Open(name, attrs) => match name.as_str() {
+                "h1" => out.push_str("# "),
+                "h2" => out.push_str("## "),
+                "h3" => out.push_str("### "),
+                "pre" => {
+                    in_pre = true;
+                    block_break(&mut out);
+                    out.push_str("```\n");
+                }
+                "code" => {
+                    in_code = true;
+                    out.push('`');
+                }
+                "em" if in_code && out.ends_with('`') => {
+                    out.insert(out.len() - 1, '*');
+                    em_wraps_code = true;
+                }
+                "em" if !in_code => out.push('*'),
+                "li" => out.push_str("- "),
+                "br" => out.push('\n'),
+                "a" => {
+                    let href = attribute(attrs, "href").map(decode_entities);
+                    if href.is_some() {
+                        out.push('[');
+                    }
+                    links.push(href);
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name.as_str() {
+                "h1" | "h2" | "h3" | "p" | "ul" => block_break(&mut out),
+                "pre" => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```");
+                    block_break(&mut out);
+                }
+                "code" => {
+                    in_code = false;
+                    out.push('`');
+                    if em_wraps_code {
+                        out.push('*');
+                        em_wraps_code = false;
+                    }
+                }
+                "em" if !in_code => out.push('*'),
+                "li" => out.push('\n'),
+                "a" => {
+                    if let Some(Some(href)) = links.pop() {
+                        let _ = write!(out, "]({href})");
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let mut md = out.trim().to_string();
+    md.push('\n');
+    md
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    /// An opening or self-closing tag with its lowercased name and raw attributes.
+    Open(String, &'a str),
+    Close(String),
+}
+
+impl Token<'_> {
+    fn is(&self, tag: &str) -> bool {
+        match self {
+            Token::Open(name, _) | Token::Close(name) => name == tag,
+            Token::Text(_) => false,
+        }
+    }
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(end) = rest[start..].find('>') else {
+            tokens.push(Token::Text(&rest[start..]));
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_lowercase()));
+        } else if !tag.starts_with('!') {
+            let tag = tag.trim_end_matches('/');
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Open(name.to_lowercase(), attrs));
+        }
+    }
+
+    tokens
+}
+
+/// Returns the value of a quoted attribute, e.g. `href` in `href="/2015/day/1" target="_blank"`.
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = format!("{name}=");
+    let start = attrs.find(&prefix)? + prefix.len();
+    let value = &attrs[start..];
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    value.find(quote).map(|end| &value[..end])
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let code = entity.strip_prefix('#')?;
+                    let code = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut last_was_space = false;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            out.push(c);
+            last_was_space = false;
+        }
+    }
+    out
+}
+
+/// Ends the current block with an empty line.
+fn block_break(out: &mut String) {
+    let trimmed = out.trim_end_matches([' ', '\n']).len();
+    out.truncate(trimmed);
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, inner_html, to_markdown, to_text};
+
+    #[test]
+    fn extracts_inner_html() {
+        let html = r#"<main><article class="day-desc"><p>one</p></article>
+<articles>no</articles><article><p>two</p></article></main>"#;
+        assert_eq!(
+            inner_html(html, "article"),
+            vec!["<p>one</p>", "<p>two</p>"]
+        );
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;&amp; &#39;b&#x27; &unknown; & c"),
+            "<a> && 'b' &unknown; & c"
+        );
+    }
+
+    #[test]
+    fn converts_to_text() {
+        assert_eq!(
+            to_text("<p>That's not the right answer;\n  your answer is <em>too low</em>. <a href=\"/2015/day/1\">[Return to Day 1]</a></p>"),
+            "That's not the right answer; your answer is too low. [Return to Day 1]"
+        );
+    }
+
+    #[test]
+    fn converts_to_markdown() {
+        let html = r#"<h2>--- Day 1: Not Quite Lisp ---</h2><p>Santa is trying to deliver presents in a <em>large apartment building</em>.</p>
+<p>For example:</p>
+<ul>
+<li><code>(())</code> and <code>()()</code> both result in floor <code><em>0</em></code>.</li>
+<li>See <a href="/2015/about" target="_blank">about</a>.</li>
+</ul>
+<pre><code>x &lt; <em>y</em>
+z
+</code></pre>
+<p>What floor?</p>"#;
+
+        assert_eq!(
+            to_markdown(html),
+            "## --- Day 1: Not Quite Lisp ---
+
+Santa is trying to deliver presents in a *large apartment building*.
+
+For example:
+
+- `(())` and `()()` both result in floor *`0`*.
+- See [about](/2015/about).
+
+```
+x < y
+z
+```
+
+What floor?
+"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+/// Module that keeps track of downloaded inputs.
+/// Next to every downloaded input, a small metadata file (`data/{year}/inputs/{day}.meta`) records when it was fetched,
+/// its length and its hash, so that inputs that were cut off or emptied afterwards can be detected.
+use std::{
+    fmt::Display,
+    fs, io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::paths;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Metadata of a downloaded input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMeta {
+    /// Unix timestamp (seconds) of the download.
+    pub fetched_at: u64,
+    pub year: Year,
+    /// Hex-encoded md5 hash of the input.
+    pub hash: String,
+    pub bytes: usize,
+}
+
+impl InputMeta {
+    #[must_use]
+    pub fn for_content(year: Year, content: &str) -> Self {
+        Self {
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            year,
+            hash: hash(content),
+            bytes: content.len(),
+        }
+    }
+
+    /// Loads the metadata of an input. Returns [`None`] if the input was not downloaded by the template.
+    pub fn load(year: Year, day: Day) -> Result<Option<Self>, Error> {
+        match fs::read_to_string(paths::input_meta_path(year, day)) {
+            Ok(s) => parse_meta(&s).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, day: Day) -> Result<(), Error> {
+        fs::write(paths::input_meta_path(self.year, day), format_meta(self))?;
+        Ok(())
+    }
+}
+
+/// A problem with an input file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    Empty,
+    /// The input is shorter than the downloaded input.
+    Truncated {
+        expected: usize,
+        actual: usize,
+    },
+    /// The input differs from the downloaded input.
+    Modified,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Empty => write!(f, "is empty"),
+            Issue::Truncated { expected, actual } => write!(
+                f,
+                "looks truncated ({actual} of {expected} downloaded bytes)"
+            ),
+            Issue::Modified => write!(f, "differs from the downloaded input"),
+        }
+    }
+}
+
+/// Checks an input against the metadata recorded when it was downloaded, if any.
+#[must_use]
+pub fn check(meta: Option<&InputMeta>, content: &str) -> Option<Issue> {
+    if content.trim().is_empty() {
+        return Some(Issue::Empty);
+    }
+
+    let meta = meta?;
+
+    if content.len() < meta.bytes {
+        Some(Issue::Truncated {
+            expected: meta.bytes,
+            actual: content.len(),
+        })
+    } else if hash(content) != meta.hash {
+        Some(Issue::Modified)
+    } else {
+        None
+    }
+}
+
+/// Whether an input exists and is not empty, i.e. whether it does not need to be downloaded.
+#[must_use]
+pub fn is_cached(year: Year, day: Day) -> bool {
+    fs::read_to_string(paths::input_path(year, day)).is_ok_and(|s| !s.trim().is_empty())
+}
+
+/// Prints a warning if an input file is empty or does not match its download.
+pub fn warn_on_issues(year: Year, day: Day, content: &str) {
+    let meta = InputMeta::load(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read input metadata: {e:?}");
+        None
+    });
+
+    if let Some(issue) = check(meta.as_ref(), content) {
+        let hint = if issue == Issue::Empty {
+            ""
+        } else {
+            " --force"
+        };
+        eprintln!(
+            "Warning: input file \"{}\" {issue}. Run `cargo download {day} --year {year}{hint}` to download it.",
+            paths::input_path(year, day)
+        );
+    }
+}
+
+fn hash(content: &str) -> String {
+    format!("{:x}", md5::compute(content))
+}
+
+fn parse_meta(s: &str) -> Result<InputMeta, Error> {
+    let mut fields = std::collections::HashMap::new();
+
+    for line in s.lines().filter(|l| !l.trim().is_empty()) {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| Error::Parser(format!("malformed metadata line: {line}")))?;
+        fields.insert(key.trim(), value.trim());
+    }
+
+    let get = |key: &str| {
+        fields
+            .get(key)
+            .copied()
+            .ok_or_else(|| Error::Parser(format!("missing field \"{key}\"")))
+    };
+    let invalid = |key: &str| Error::Parser(format!("field \"{key}\" is invalid"));
+
+    Ok(InputMeta {
+        fetched_at: get("fetched_at")?
+            .parse()
+            .map_err(|_| invalid("fetched_at"))?,
+        year: get("year")?.parse().map_err(|_| invalid("year"))?,
+        hash: get("md5")?.to_string(),
+        bytes: get("bytes")?.parse().map_err(|_| invalid("bytes"))?,
+    })
+}
+
+fn format_meta(meta: &InputMeta) -> String {
+    format!(
+        "fetched_at={}\nyear={}\nmd5={}\nbytes={}\n",
+        meta.fetched_at, meta.year, meta.hash, meta.bytes
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, format_meta, parse_meta, InputMeta, Issue};
+    use crate::year;
+
+    #[test]
+    fn roundtrips_meta() {
+        let meta = InputMeta::for_content(year!(2015), "(()(\n");
+        assert_eq!(meta.bytes, 5);
+        assert_eq!(meta.hash.len(), 32);
+        assert_eq!(parse_meta(&format_meta(&meta)).unwrap(), meta);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_missing_fields() {
+        parse_meta("fetched_at=1\nyear=2015\n").unwrap();
+    }
+
+    #[test]
+    fn detects_issues() {
+        let meta = InputMeta::for_content(year!(2015), "1\n2\n3\n");
+
+        assert_eq!(check(Some(&meta), "1\n2\n3\n"), None);
+        assert_eq!(check(None, "1\n2\n"), None);
+        assert_eq!(check(None, "\n"), Some(Issue::Empty));
+        assert_eq!(check(Some(&meta), ""), Some(Issue::Empty));
+        assert_eq!(
+            check(Some(&meta), "1\n2\n"),
+            Some(Issue::Truncated {
+                expected: 6,
+                actual: 4
+            })
+        );
+        assert_eq!(check(Some(&meta), "1\n2\n4\n"), Some(Issue::Modified));
+    }
+}
@@ -0,0 +1,136 @@
+/// Module that selects the input a solution runs against, passed as `--input <path>`, `--input -` (stdin)
+/// or `--example [N]`. By default, solutions run against the puzzle input in `data/{year}/inputs`.
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, Read},
+};
+
+use crate::template::{paths, try_read_file};
+use crate::{Day, Year};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input of the day.
+    #[default]
+    Puzzle,
+    /// The example of the day, or the example of a part (e.g. `01-2.txt`).
+    Example(Option<u8>),
+    File(String),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        if let Some(path) = args.opt_value_from_str::<_, String>("--input")? {
+            return Ok(if path == "-" {
+                InputSource::Stdin
+            } else {
+                InputSource::File(path)
+            });
+        }
+
+        Ok(match args.opt_value_from_str::<_, u8>("--example")? {
+            Some(0) => InputSource::Example(None),
+            Some(part) => InputSource::Example(Some(part)),
+            None => InputSource::Puzzle,
+        })
+    }
+
+    /// Converts the source back into arguments that can be forwarded to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(part) => vec!["--example".into(), part.unwrap_or(0).to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.clone()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Whether this is the puzzle input. Answers to other inputs are neither verified nor submitted.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    pub fn read(&self, year: Year, day: Day) -> io::Result<String> {
+        match self {
+            InputSource::Puzzle => try_read_file("inputs", year, day),
+            InputSource::Example(None) => try_read_file("examples", year, day),
+            InputSource::Example(Some(part)) => fs::read_to_string(format!(
+                "{}/examples/{day}-{part}.txt",
+                paths::data_dir(year)
+            )),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Returns the arguments of the current process for parsing with pico-args.
+/// pico-args only supports options with a mandatory value, so a bare `--example` is passed on as `--example 0`.
+#[must_use]
+pub fn args_from_env() -> pico_args::Arguments {
+    pico_args::Arguments::from_vec(normalize_args(env::args_os().skip(1).collect()))
+}
+
+fn normalize_args(mut args: Vec<OsString>) -> Vec<OsString> {
+    if let Some(pos) = args.iter().position(|arg| arg == "--example") {
+        let has_value = args
+            .get(pos + 1)
+            .and_then(|arg| arg.to_str())
+            .is_some_and(|arg| arg.parse::<u8>().is_ok());
+
+        if !has_value {
+            args.insert(pos + 1, "0".into());
+        }
+    }
+
+    args
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::{normalize_args, InputSource};
+
+    fn parse(args: &[&str]) -> InputSource {
+        let args = normalize_args(args.iter().map(OsString::from).collect());
+        InputSource::from_args(&mut pico_args::Arguments::from_vec(args)).unwrap()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&["--time"]), InputSource::Puzzle);
+        assert_eq!(parse(&["--example"]), InputSource::Example(None));
+        assert_eq!(parse(&["--example", "--time"]), InputSource::Example(None));
+        assert_eq!(parse(&["--example", "2"]), InputSource::Example(Some(2)));
+        assert_eq!(
+            parse(&["--input", "stress.txt"]),
+            InputSource::File("stress.txt".into())
+        );
+        assert_eq!(parse(&["--input", "-"]), InputSource::Stdin);
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+            InputSource::File("stress.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let args = source.to_args();
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            assert_eq!(parse(&args), source);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+/// Resource limits for solution runs, so that a single hanging or runaway day does not block a whole run.
+/// The timeout is enforced by the process that spawns a solution binary, the memory cap by the solution binary itself.
+use std::io::{self, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Interval in which a running child is checked for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Limits passed as `--timeout <duration>` and `--max-memory <MB>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time after which a solution binary is killed.
+    pub timeout: Option<Duration>,
+    /// Maximum memory a solution binary may use, in megabytes.
+    pub max_memory_mb: Option<u64>,
+}
+
+impl Limits {
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            max_memory_mb: args.opt_value_from_str("--max-memory")?,
+        })
+    }
+
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.max_memory_mb.is_some()
+    }
+
+    /// Converts the memory cap into arguments that can be forwarded to a solution binary.
+    /// The timeout is not forwarded, it is enforced by the parent process.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self.max_memory_mb {
+            Some(mb) => vec!["--max-memory".into(), mb.to_string()],
+            None => vec![],
+        }
+    }
+}
+
+/// Parses a duration like `30`, `30s`, `500ms` or `2m`. Plain numbers are seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (value, unit) = s
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or((s, ""), |i| s.split_at(i));
+
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("\"{s}\" is not a duration, expecting e.g. 30s, 500ms or 2m"))?;
+
+    match unit {
+        "" | "s" => Ok(Duration::from_secs(value)),
+        "ms" => Ok(Duration::from_millis(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        _ => Err(format!(
+            "\"{unit}\" is not a unit of time, expecting ms, s or m"
+        )),
+    }
+}
+
+/// Caps the address space of the current process at `mb` megabytes.
+/// Allocations beyond the cap fail, which aborts the process.
+#[cfg(unix)]
+pub fn apply_memory_limit(mb: u64) -> io::Result<()> {
+    let bytes = mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+
+    // SAFETY: `setrlimit` only reads the passed struct.
+    match unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn apply_memory_limit(_mb: u64) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "memory caps are only supported on unix",
+    ))
+}
+
+/// How a child process finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Finished(ExitStatus),
+    /// The child ran longer than the timeout and was killed.
+    TimedOut(Duration),
+}
+
+/// Runs a command, killing it if it exceeds `timeout`.
+/// If `capture` is set, its output is returned instead of forwarded to stdout / stderr.
+pub fn run_child(
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+    capture: bool,
+) -> io::Result<(Exit, String)> {
+    if capture {
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    } else {
+        cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+    }
+
+    let mut child = cmd.spawn()?;
+
+    // drain the pipes while waiting, a child that fills them would block otherwise.
+    let stdout = child.stdout.take().map(|s| thread::spawn(|| read_all(s)));
+    let stderr = child.stderr.take().map(|s| thread::spawn(|| read_all(s)));
+
+    let exit = wait(&mut child, timeout)?;
+
+    let mut output = String::new();
+    for reader in [stdout, stderr].into_iter().flatten() {
+        output.push_str(&reader.join().unwrap_or_default());
+    }
+
+    Ok((exit, output))
+}
+
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<Exit> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Exit::Finished);
+    };
+
+    let started = Instant::now();
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Exit::Finished(status));
+        }
+
+        if started.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(Exit::TimedOut(timeout));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn read_all(mut reader: impl Read) -> String {
+    let mut buf = vec![];
+    let _ = reader.read_to_end(&mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_duration;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("10h").is_err());
+        assert!(parse_duration("-1").is_err());
+    }
+}
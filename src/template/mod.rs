@@ -1,48 +1,156 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Year};
+use std::{env, fs, io};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+#[cfg(feature = "native")]
+pub mod aoc_http;
+pub mod bench;
 pub mod commands;
+pub mod examples;
+pub mod history;
+pub mod html;
+pub mod input_cache;
+pub mod input_source;
+pub mod limits;
+pub mod memory;
+pub mod ocr;
+pub mod outcome;
+pub mod paths;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
+pub mod run_cache;
 pub mod runner;
+pub mod scaffold_template;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the year configured in `AOC_YEAR`, if set.
+#[must_use]
+pub fn get_year() -> Option<Year> {
+    match env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok(),
+        Err(_) => None,
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    let f = try_read_file(folder, year, day);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string, returning an error if the file can't be read.
+/// Prints a warning if an input is empty or looks truncated.
+pub fn try_read_file(folder: &str, year: Year, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join(paths::data_path(year, folder, day));
+    let content = fs::read_to_string(filepath)?;
+
+    if folder == "inputs" {
+        input_cache::warn_on_issues(year, day, &content);
+    }
+
+    Ok(content)
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(paths::data_dir(year))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+///
+/// Besides `main`, this generates `run_parts`, which runs both parts (or the part selected with `--part`) against an input.
+/// It is the entry point used by the solution registry to run solutions in-process.
+///
+/// If a parse function is passed, e.g. `solution!(2015, 6, parse)`, the input is parsed once and both parts
+/// receive a reference to the parsed input. The parse function returns a [`Result`] and is timed separately.
+///
+/// Alternative implementations of a part can be passed as variants, e.g.
+/// `solution!(2015, 6, parse, part_one: [part_one_intervals], part_two: [])`. Variants run after the part itself,
+/// must produce the same answer and are compared in a benchmark table, see [`runner::Variants`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($year:expr, $day:expr $(, part_one: [$($one:ident),* $(,)?])? $(, part_two: [$($two:ident),* $(,)?])? $(,)?) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        #[allow(dead_code)]
+        fn main() {
+            advent_of_code::template::runner::run_bin(YEAR, DAY, run_parts);
+        }
+
+        /// Runs both parts of the solution against `input`.
+        pub fn run_parts(
+            input: &str,
+            config: &advent_of_code::template::runner::RunConfig,
+        ) -> Vec<advent_of_code::template::report::PartReport> {
+            use advent_of_code::template::runner::*;
+            let mut reports = vec![];
+            if config.runs_part(1) {
+                let mut variants = Variants::new("part_one", run_part(part_one, input, YEAR, DAY, 1, config));
+                $($(variants.run(stringify!($one), $one, input, config);)*)?
+                reports.push(variants.finish(YEAR, DAY, config));
+            }
+            if config.runs_part(2) {
+                let mut variants = Variants::new("part_two", run_part(part_two, input, YEAR, DAY, 2, config));
+                $($(variants.run(stringify!($two), $two, input, config);)*)?
+                reports.push(variants.finish(YEAR, DAY, config));
+            }
+            reports
+        }
+    };
+    ($year:expr, $day:expr, $parse:ident $(, part_one: [$($one:ident),* $(,)?])? $(, part_two: [$($two:ident),* $(,)?])? $(,)?) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        #[allow(dead_code)]
         fn main() {
+            advent_of_code::template::runner::run_bin(YEAR, DAY, run_parts);
+        }
+
+        /// Parses `input` once and runs both parts of the solution against the parsed input.
+        pub fn run_parts(
+            input: &str,
+            config: &advent_of_code::template::runner::RunConfig,
+        ) -> Vec<advent_of_code::template::report::PartReport> {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let (parsed, report) = run_parse($parse, input, DAY, config);
+            let mut reports = vec![report];
+            // calling the parts through closures lets them take e.g. `&[T]` for a parsed `Vec<T>`.
+            if let Some(parsed) = parsed {
+                if config.runs_part(1) {
+                    let mut variants = Variants::new("part_one", run_part(|p| part_one(p), &parsed, YEAR, DAY, 1, config));
+                    $($(variants.run(stringify!($one), |p| $one(p), &parsed, config);)*)?
+                    reports.push(variants.finish(YEAR, DAY, config));
+                }
+                if config.runs_part(2) {
+                    let mut variants = Variants::new("part_two", run_part(|p| part_two(p), &parsed, YEAR, DAY, 2, config));
+                    $($(variants.run(stringify!($two), |p| $two(p), &parsed, config);)*)?
+                    reports.push(variants.finish(YEAR, DAY, config));
+                }
+            }
+            reports
         }
     };
 }
@@ -0,0 +1,120 @@
+/// Module that reads letters drawn as ASCII art, as some puzzles display their answer on a screen of lit pixels.
+/// Supports the 6 pixel high font used by advent of code. Lit pixels are any characters but `.` and spaces.
+use std::fmt::Display;
+
+const HEIGHT: usize = 6;
+
+/// The letters of the font, with their empty columns trimmed.
+static FONT: [(char, [&str; HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// An answer drawn as ASCII art. Displays as the letters it shows, or as the drawing if it can't be read.
+///
+/// ```
+/// # use advent_of_code::template::ocr::Ocr;
+/// let screen = "#..#.###.\n#..#..#..\n####..#..\n#..#..#..\n#..#..#..\n#..#.###.";
+/// assert_eq!(Ocr(screen.into()).to_string(), "HI");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ocr(pub String);
+
+impl Display for Ocr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match read(&self.0) {
+            Some(letters) => write!(f, "{letters}"),
+            None => write!(f, "{}", self.0.trim_end()),
+        }
+    }
+}
+
+/// Reads the letters of a drawing. Returns [`None`] if the drawing is not 6 rows high or contains unknown letters.
+#[must_use]
+pub fn read(drawing: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = drawing
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.chars().map(|c| c != '.' && c != ' ').collect())
+        .collect();
+
+    if rows.len() != HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let is_lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&true));
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if !is_lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && is_lit(x) {
+            x += 1;
+        }
+
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (start..x)
+                    .map(|i| if row.get(i) == Some(&true) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let (letter, _) = FONT.iter().find(|(_, pixels)| pixels[..] == glyph[..])?;
+        letters.push(*letter);
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read, Ocr};
+
+    #[test]
+    fn reads_letters() {
+        let screen = "
+.##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####
+";
+        assert_eq!(read(screen).as_deref(), Some("ABCEFGHIJKLOPRSUYZ"));
+    }
+
+    #[test]
+    fn displays_drawings() {
+        assert_eq!(read("#\n#\n#"), None);
+        assert_eq!(Ocr("#.#\n.#.\n".into()).to_string(), "#.#\n.#.");
+        assert_eq!(
+            Ocr("█  █\n█  █\n████\n█  █\n█  █\n█  █".into()).to_string(),
+            "H"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+/// Module that turns the return values of solution parts into answers.
+/// Parts return an [`Option`] or a [`Result`] of any displayable answer, e.g. a number, a string or an [`Ocr`] drawing.
+///
+/// [`Ocr`]: crate::template::ocr::Ocr
+use std::fmt::{Debug, Display};
+
+/// What running a part of a solution produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    /// The part returned [`None`], e.g. because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error.
+    Failed(String),
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Outcome::Answer(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Outcome::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Return types of solution parts.
+pub trait IntoOutcome {
+    fn outcome(&self) -> Outcome;
+}
+
+impl<T: Display> IntoOutcome for Option<T> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Answer(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+/// Errors are printed with their [`Debug`] representation, so that plain `#[derive(Debug)]` error enums can be used.
+impl<T: Display, E: Debug> IntoOutcome for Result<T, E> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            Err(e) => Outcome::Failed(format!("{e:?}")),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{IntoOutcome, Outcome};
+    use crate::template::ocr::Ocr;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum ParseError {
+        NotANumber(String),
+    }
+
+    #[test]
+    fn converts_return_types() {
+        assert_eq!(Some(42).outcome(), Outcome::Answer("42".into()));
+        assert_eq!(None::<u32>.outcome(), Outcome::Unsolved);
+        assert_eq!(
+            Ok::<_, ParseError>("abc").outcome(),
+            Outcome::Answer("abc".into())
+        );
+        assert_eq!(
+            Err::<u32, _>(ParseError::NotANumber("x".into())).outcome(),
+            Outcome::Failed("NotANumber(\"x\")".into())
+        );
+        assert_eq!(
+            Some(Ocr("###\n.#.\n.#.\n.#.\n.#.\n###".into())).outcome(),
+            Outcome::Answer("I".into())
+        );
+    }
+}
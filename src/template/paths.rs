@@ -0,0 +1,69 @@
+/// Locations of the files that belong to a year of advent of code.
+/// Solutions live in `src/bin/{year}-{day}.rs`, everything else in `data/{year}`.
+use std::path::Path;
+
+use crate::{Day, Year};
+
+/// The name of the solution binary, e.g. `2015-01`.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
+
+#[must_use]
+pub fn bin_path(year: Year, day: Day) -> String {
+    format!("src/bin/{}.rs", bin_name(year, day))
+}
+
+/// Whether the solution of a day has been scaffolded.
+#[must_use]
+pub fn is_scaffolded(year: Year, day: Day) -> bool {
+    Path::new(&bin_path(year, day)).exists()
+}
+
+#[must_use]
+pub fn data_dir(year: Year) -> String {
+    format!("data/{year}")
+}
+
+/// Path of a per-day text file in a data folder, e.g. `data/2015/inputs/01.txt`.
+#[must_use]
+pub fn data_path(year: Year, folder: &str, day: Day) -> String {
+    format!("{}/{folder}/{day}.txt", data_dir(year))
+}
+
+#[must_use]
+pub fn input_path(year: Year, day: Day) -> String {
+    data_path(year, "inputs", day)
+}
+
+/// Path of the metadata recorded when an input was downloaded, see [`crate::template::input_cache`].
+#[must_use]
+pub fn input_meta_path(year: Year, day: Day) -> String {
+    format!("{}/inputs/{day}.meta", data_dir(year))
+}
+
+/// Cached results of the last run of a day, see [`crate::template::run_cache`].
+#[must_use]
+pub fn run_cache_path(year: Year, day: Day) -> String {
+    format!("{}/cache/{day}.jsonl", data_dir(year))
+}
+
+#[must_use]
+pub fn example_path(year: Year, day: Day) -> String {
+    data_path(year, "examples", day)
+}
+
+#[must_use]
+pub fn puzzle_path(year: Year, day: Day) -> String {
+    format!("{}/puzzles/{day}.md", data_dir(year))
+}
+
+/// Path of a user-defined scaffold template, see [`crate::template::scaffold_template`].
+#[must_use]
+pub fn template_path(name: &str) -> String {
+    format!(
+        "{}/{name}.rs",
+        crate::template::scaffold_template::TEMPLATES_DIR
+    )
+}
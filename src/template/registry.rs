@@ -0,0 +1,48 @@
+/// Solutions compiled into the main binary, so that commands can run them without spawning a process per day.
+/// The list of solutions is generated by `build.rs` from the contents of `src/bin` when the `in_process` feature is enabled.
+use std::{fmt::Display, io, panic};
+
+use crate::template::{input_source::InputSource, report::PartReport, runner::RunConfig};
+use crate::{Day, Year};
+
+/// Runs all parts of a solution against an input. Generated by the `solution!` macro as `run_parts`.
+pub type RunPartsFn = fn(&str, &RunConfig) -> Vec<PartReport>;
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    pub run_parts: RunPartsFn,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    MissingInput(io::Error),
+    Panicked,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingInput(e) => write!(f, "could not open input file: {e}"),
+            Error::Panicked => write!(f, "solution panicked."),
+        }
+    }
+}
+
+impl Solution {
+    /// Runs the solution against an input of its day.
+    /// A panic inside the solution is caught and returned as an error.
+    pub fn run(&self, config: &RunConfig, source: &InputSource) -> Result<Vec<PartReport>, Error> {
+        let input = source
+            .read(self.year, self.day)
+            .map_err(Error::MissingInput)?;
+        panic::catch_unwind(|| (self.run_parts)(&input, config)).map_err(|_| Error::Panicked)
+    }
+}
+
+/// Finds the solution for a given year and day.
+#[must_use]
+pub fn find(solutions: &[Solution], year: Year, day: Day) -> Option<&Solution> {
+    solutions.iter().find(|s| s.year == year && s.day == day)
+}
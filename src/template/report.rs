@@ -0,0 +1,423 @@
+/// Machine-readable results of solution binaries.
+/// When the environment variable `AOC_REPORT_FILE` is set, the runner appends one JSON record per part to that file.
+/// Commands that spawn solution binaries read these records instead of parsing their console output.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::template::memory::MemStats;
+use crate::Day;
+
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The result of running one part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub day: Day,
+    /// The part number, `0` for the parse phase of solutions with a parse function.
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Median execution time of the part.
+    pub duration_nanos: u128,
+    pub samples: usize,
+    pub success: bool,
+    /// Whether the answer matches the expected answer, if one is known.
+    pub correct: Option<bool>,
+    /// The error returned by the part, if it failed.
+    pub error: Option<String>,
+    /// Memory used by the part, for `--mem` runs. Written as `allocations`, `allocated_bytes` and `peak_bytes`.
+    pub mem: Option<MemStats>,
+}
+
+impl PartReport {
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let answer = self
+            .answer
+            .as_deref()
+            .map_or_else(|| "null".into(), escape_string);
+        let error = self
+            .error
+            .as_deref()
+            .map_or_else(|| "null".into(), escape_string);
+
+        // memory statistics are only written for `--mem` runs.
+        let mem = self.mem.map_or_else(String::new, |mem| {
+            format!(
+                r#","allocations":{},"allocated_bytes":{},"peak_bytes":{}"#,
+                mem.allocations, mem.allocated_bytes, mem.peak_bytes
+            )
+        });
+
+        format!(
+            r#"{{"day":{},"part":{},"answer":{},"duration_nanos":{},"samples":{},"success":{},"correct":{},"error":{}{}}}"#,
+            self.day.into_inner(),
+            self.part,
+            answer,
+            self.duration_nanos,
+            self.samples,
+            self.success,
+            self.correct
+                .map_or_else(|| "null".into(), |b| b.to_string()),
+            error,
+            mem
+        )
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let fields = parse_object(s)?;
+
+        let get = |key: &str| {
+            fields
+                .get(key)
+                .ok_or_else(|| Error::Parser(format!("missing field \"{key}\"")))
+        };
+
+        let number = |key: &str| match get(key)? {
+            Value::Number(n) => Ok(n.as_str()),
+            _ => Err(Error::Parser(format!("field \"{key}\" is not a number"))),
+        };
+
+        let invalid = |key: &str| Error::Parser(format!("field \"{key}\" is out of range"));
+
+        Ok(Self {
+            day: number("day")?.parse().map_err(|_| invalid("day"))?,
+            part: number("part")?.parse().map_err(|_| invalid("part"))?,
+            answer: match get("answer")? {
+                Value::String(s) => Some(s.clone()),
+                Value::Null => None,
+                _ => return Err(Error::Parser("field \"answer\" is not a string".into())),
+            },
+            duration_nanos: number("duration_nanos")?
+                .parse()
+                .map_err(|_| invalid("duration_nanos"))?,
+            samples: number("samples")?.parse().map_err(|_| invalid("samples"))?,
+            success: match get("success")? {
+                Value::Bool(b) => *b,
+                _ => return Err(Error::Parser("field \"success\" is not a boolean".into())),
+            },
+            correct: match fields.get("correct") {
+                Some(Value::Bool(b)) => Some(*b),
+                Some(Value::Null) | None => None,
+                _ => return Err(Error::Parser("field \"correct\" is not a boolean".into())),
+            },
+            error: match fields.get("error") {
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(Value::Null) | None => None,
+                _ => return Err(Error::Parser("field \"error\" is not a string".into())),
+            },
+            mem: match fields.get("allocations") {
+                Some(_) => Some(MemStats {
+                    allocations: number("allocations")?
+                        .parse()
+                        .map_err(|_| invalid("allocations"))?,
+                    allocated_bytes: number("allocated_bytes")?
+                        .parse()
+                        .map_err(|_| invalid("allocated_bytes"))?,
+                    peak_bytes: number("peak_bytes")?
+                        .parse()
+                        .map_err(|_| invalid("peak_bytes"))?,
+                }),
+                None => None,
+            },
+        })
+    }
+}
+
+/// Appends a record to the report file, if the current process was asked to write one.
+pub fn write(report: &PartReport) -> Result<(), Error> {
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", report.to_json())?;
+    Ok(())
+}
+
+/// Reads all records from a report file. A missing file is treated as an empty report.
+pub fn read(path: &Path) -> Result<Vec<PartReport>, Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(PartReport::from_json)
+            .collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The subset of JSON values used by reports: flat objects without nested arrays or objects.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+}
+
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            other => Err(Error::Parser(format!(
+                "expected '{expected}', found {other:?}"
+            ))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(out),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('u') => {
+                        let hex: String = self.chars.by_ref().take(4).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| Error::Parser(format!("invalid escape \\u{hex}")))?;
+                        out.push(c);
+                    }
+                    Some(c) => out.push(c),
+                    None => break,
+                },
+                Some(c) => out.push(c),
+                None => break,
+            }
+        }
+        Err(Error::Parser("unterminated string".into()))
+    }
+
+    fn literal(&mut self) -> String {
+        let mut out = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_alphanumeric() || "+-.".contains(*c))
+        {
+            out.push(c);
+        }
+        out
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'"') {
+            return self.string().map(Value::String);
+        }
+        match self.literal().as_str() {
+            "null" => Ok(Value::Null),
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            n if is_number(n) => Ok(Value::Number(n.into())),
+            other => Err(Error::Parser(format!("unexpected value \"{other}\""))),
+        }
+    }
+}
+
+/// Whether a literal is a JSON number. Rejects tokens like `inf` or `NaN` that [`f64`] would parse.
+fn is_number(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c)) && s.parse::<f64>().is_ok()
+}
+
+fn parse_object(s: &str) -> Result<HashMap<String, Value>, Error> {
+    let mut parser = Parser::new(s);
+    let mut fields = HashMap::new();
+
+    parser.expect('{')?;
+    parser.skip_whitespace();
+
+    if parser.chars.next_if_eq(&'}').is_none() {
+        loop {
+            parser.skip_whitespace();
+            let key = parser.string()?;
+            parser.expect(':')?;
+            let value = parser.value()?;
+            fields.insert(key, value);
+
+            parser.skip_whitespace();
+            match parser.chars.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                other => {
+                    return Err(Error::Parser(format!(
+                        "expected ',' or '}}', found {other:?}"
+                    )))
+                }
+            }
+        }
+    }
+
+    // a truncated or concatenated report must not parse as the first object.
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        Some(c) => Err(Error::Parser(format!("unexpected {c:?} after the object"))),
+        None => Ok(fields),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_object, PartReport, Value};
+    use crate::day;
+    use crate::template::memory::MemStats;
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            day: day!(6),
+            part: 2,
+            answer: Some("multi\n\"line\"\tanswer".into()),
+            duration_nanos: 74_130,
+            samples: 100,
+            success: true,
+            correct: Some(false),
+            error: None,
+            mem: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_report() {
+        let report = get_mock_report();
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+    }
+
+    #[test]
+    fn roundtrips_missing_answer() {
+        let report = PartReport {
+            answer: None,
+            success: false,
+            correct: None,
+            error: Some("ParseError(\"1x2\")".into()),
+            ..get_mock_report()
+        };
+        let json = report.to_json();
+        assert!(json.contains(r#""answer":null"#));
+        assert_eq!(PartReport::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn roundtrips_memory() {
+        let report = PartReport {
+            mem: Some(MemStats {
+                allocations: 3,
+                allocated_bytes: 4096,
+                peak_bytes: 2048,
+            }),
+            ..get_mock_report()
+        };
+        let json = report.to_json();
+        assert!(json.ends_with(r#","allocations":3,"allocated_bytes":4096,"peak_bytes":2048}"#));
+        assert_eq!(PartReport::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn format_report() {
+        let report = PartReport {
+            answer: Some("42".into()),
+            ..get_mock_report()
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":6,"part":2,"answer":"42","duration_nanos":74130,"samples":100,"success":true,"correct":false,"error":null}"#
+        );
+    }
+
+    #[test]
+    fn parses_whitespace_and_escapes() {
+        let fields = parse_object(r#" { "a" : "é (samples) @" , "b": -1.5e3 } "#).unwrap();
+        assert_eq!(fields["a"], Value::String("é (samples) @".into()));
+        assert_eq!(fields["b"], Value::Number("-1.5e3".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_missing_fields() {
+        PartReport::from_json(r#"{"day":1,"part":1}"#).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_invalid_day() {
+        PartReport::from_json(
+            r#"{"day":26,"part":1,"answer":null,"duration_nanos":1,"samples":1,"success":false}"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_trailing_data() {
+        let json = get_mock_report().to_json();
+        assert!(PartReport::from_json(&format!("{json} \n")).is_ok());
+        assert!(PartReport::from_json(&format!("{json}{json}")).is_err());
+        assert!(PartReport::from_json(&format!("{json},")).is_err());
+        assert!(parse_object("{} x").is_err());
+    }
+
+    #[test]
+    fn rejects_non_json_numbers() {
+        for n in ["inf", "NaN", "infinity", "-inf"] {
+            let json = format!(
+                r#"{{"day":1,"part":1,"answer":null,"duration_nanos":{n},"samples":1,"success":false}}"#
+            );
+            assert!(PartReport::from_json(&json).is_err(), "accepted {n}");
+        }
+    }
+}
@@ -0,0 +1,179 @@
+/// Module that caches the results of solution runs, so that `all` can skip days that did not change.
+/// A day's results (`data/{year}/cache/{day}.jsonl`) are stored along with a fingerprint of everything that can
+/// affect them: the solution, the shared library sources, the input and the settings of the run.
+/// Results are only reused while the fingerprint matches.
+use std::{fs, io, path::Path};
+
+use crate::template::paths;
+use crate::template::report::{self, PartReport};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    Report(report::Error),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<report::Error> for Error {
+    fn from(e: report::Error) -> Self {
+        Error::Report(e)
+    }
+}
+
+/// Computes fingerprints of days. Sources shared by all days are hashed once.
+pub struct Fingerprinter {
+    shared: md5::Digest,
+}
+
+impl Fingerprinter {
+    /// `settings` describes how solutions are run, e.g. their build profile and benchmark settings.
+    pub fn new(settings: &str) -> Result<Self, Error> {
+        let mut files = vec!["Cargo.toml".into()];
+        collect_sources(Path::new("src"), &mut files)?;
+        files.sort();
+
+        let mut context = md5::Context::new();
+        context.consume(settings);
+
+        for file in files {
+            consume_file(&mut context, &file)?;
+        }
+
+        Ok(Self {
+            shared: context.compute(),
+        })
+    }
+
+    /// Fingerprint of a day, combining the shared sources with its solution and its input.
+    pub fn fingerprint(&self, year: Year, day: Day) -> Result<String, Error> {
+        let mut context = md5::Context::new();
+        context.consume(self.shared.0);
+        consume_file(&mut context, &paths::bin_path(year, day))?;
+        consume_file(&mut context, &paths::input_path(year, day))?;
+        Ok(format!("{:x}", context.compute()))
+    }
+}
+
+/// Loads the cached results of a day. Returns [`None`] if there are none or they have a different fingerprint.
+pub fn load(year: Year, day: Day, fingerprint: &str) -> Result<Option<Vec<PartReport>>, Error> {
+    match fs::read_to_string(paths::run_cache_path(year, day)) {
+        Ok(s) => parse_cache(&s, fingerprint),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn store(year: Year, day: Day, fingerprint: &str, reports: &[PartReport]) -> Result<(), Error> {
+    let path = paths::run_cache_path(year, day);
+
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, format_cache(fingerprint, reports))?;
+    Ok(())
+}
+
+/// Collects the paths of all files below `dir`, except for the solutions in `src/bin`.
+fn collect_sources(dir: &Path, files: &mut Vec<String>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            if path != Path::new("src/bin") {
+                collect_sources(&path, files)?;
+            }
+        } else {
+            files.push(path.to_string_lossy().into_owned());
+        }
+    }
+
+    Ok(())
+}
+
+/// Hashes the path and the contents of a file. Missing files are hashed as empty.
+fn consume_file(context: &mut md5::Context, path: &str) -> io::Result<()> {
+    context.consume(path);
+
+    match fs::read(path) {
+        Ok(content) => {
+            context.consume(content.len().to_le_bytes());
+            context.consume(content);
+            Ok(())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn format_cache(fingerprint: &str, reports: &[PartReport]) -> String {
+    let mut lines = vec![fingerprint.to_string()];
+    lines.extend(reports.iter().map(PartReport::to_json));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn parse_cache(s: &str, fingerprint: &str) -> Result<Option<Vec<PartReport>>, Error> {
+    let mut lines = s.lines().filter(|l| !l.trim().is_empty());
+
+    let cached_fingerprint = lines
+        .next()
+        .ok_or_else(|| Error::Parser("missing fingerprint".into()))?;
+
+    if cached_fingerprint.trim() != fingerprint {
+        return Ok(None);
+    }
+
+    let reports = lines
+        .map(PartReport::from_json)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(reports))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_cache, parse_cache};
+    use crate::day;
+    use crate::template::report::PartReport;
+
+    fn report(part: u8) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            answer: Some("42".into()),
+            duration_nanos: 1_200,
+            samples: 10,
+            success: true,
+            correct: Some(true),
+            error: None,
+            mem: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_cache() {
+        let reports = vec![report(1), report(2)];
+        let s = format_cache("abc", &reports);
+        assert_eq!(parse_cache(&s, "abc").unwrap(), Some(reports));
+    }
+
+    #[test]
+    fn misses_on_different_fingerprint() {
+        let s = format_cache("abc", &[report(1)]);
+        assert_eq!(parse_cache(&s, "def").unwrap(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_empty_cache() {
+        parse_cache("", "abc").unwrap();
+    }
+}
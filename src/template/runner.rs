@@ -1,63 +1,350 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-use std::fmt::Display;
+use crate::template::answers::Answers;
+use crate::template::bench::{BenchOptions, Stats};
+use crate::template::input_source::{self, InputSource};
+use crate::template::limits;
+use crate::template::memory::{self, MemStats};
+use crate::template::outcome::{IntoOutcome, Outcome};
+use crate::template::registry::RunPartsFn;
+use crate::template::report::{self, PartReport};
+use crate::template::submissions::{self, Verdict};
+use crate::template::{aoc_client, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+use std::fmt::{Debug, Display};
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::process;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Settings for running a solution, read from the command-line of a solution binary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Bench each part instead of running it once.
+    pub time: bool,
+    pub bench: BenchOptions,
+    /// The part to submit to advent of code, if any.
+    pub submit: Option<u8>,
+    /// Submit even if earlier submissions show that the answer is wrong.
+    pub force: bool,
+    /// The solution runs against an input other than the puzzle input, see [`InputSource`].
+    /// Its answers are neither verified nor submitted.
+    pub custom_input: bool,
+    /// Only run this part, if set.
+    pub part: Option<u8>,
+    /// Cap the memory of the solution binary at this many megabytes, see [`limits::apply_memory_limit`].
+    pub max_memory_mb: Option<u64>,
+    /// Count the allocations of each part, see [`memory`].
+    pub mem: bool,
+}
+
+impl RunConfig {
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            time: args.contains("--time"),
+            bench: BenchOptions::from_args(args)?,
+            submit: args.opt_value_from_str("--submit")?,
+            force: args.contains("--force"),
+            custom_input: false,
+            part: args.opt_value_from_fn("--part", parse_part)?,
+            max_memory_mb: args.opt_value_from_str("--max-memory")?,
+            mem: args.contains("--mem"),
+        })
+    }
+
+    /// Whether a part should be run, i.e. no part was selected or this part was.
+    #[must_use]
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// Parses a part number passed as `--part`.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s.parse() {
+        Ok(part @ (1 | 2)) => Ok(part),
+        _ => Err(format!("\"{s}\" is not a part, expecting 1 or 2")),
+    }
+}
+
+/// Entry point of a solution binary: reads the input for `year` and `day` and runs all parts of the solution.
+/// If requested, the results are written to the report file afterwards.
+pub fn run_bin(year: Year, day: Day, run_parts: RunPartsFn) {
+    let mut args = input_source::args_from_env();
+
+    let (mut config, source) = RunConfig::from_args(&mut args)
+        .and_then(|config| Ok((config, InputSource::from_args(&mut args)?)))
+        .unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input: {e}. Format: cargo solve 1 --time --submit 1 --input <path>");
+            process::exit(1);
+        });
+    config.custom_input = !source.is_puzzle();
+
+    if let Some(submit) = config.submit.filter(|&part| !config.runs_part(part)) {
+        eprintln!(
+            "Can't submit part {submit} when only running part {}.",
+            config.part.unwrap_or_default()
+        );
+        process::exit(1);
+    }
+
+    if config.mem && !memory::IS_INSTALLED {
+        eprintln!("Not counting allocations, the solution was built without the `mem` feature.");
+    }
+
+    if let Some(mb) = config.max_memory_mb {
+        if let Err(e) = limits::apply_memory_limit(mb) {
+            eprintln!("Failed to cap memory at {mb}MB: {e}");
+        }
+    }
+
+    let input = source.read(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read input: {e}");
+        process::exit(1);
+    });
+
+    for report in run_parts(&input, &config) {
+        if let Err(e) = report::write(&report) {
+            eprintln!("Failed to write report: {e:?}");
+        }
+    }
+}
+
+/// Runs a solution part and prints its result. Returns a record of the run.
+/// Parts can return any [`IntoOutcome`], i.e. an [`Option`] or a [`Result`] of a displayable answer.
+/// The answer is not submitted yet, see [`Variants::finish`].
+pub fn run_part<I: Clone, R: IntoOutcome>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    config: &RunConfig,
+) -> PartReport {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, mem) = run_timed(func, input, config, |result| {
+        print_result(&result.outcome(), &part_str, "");
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let outcome = result.outcome();
+    let answer = outcome.answer().map(ToString::to_string);
+    let answers = if config.custom_input {
+        Answers::default()
+    } else {
+        Answers::load(year).unwrap_or_else(|e| {
+            eprintln!("Failed to read expected answers: {e:?}");
+            Answers::default()
+        })
+    };
+    let correct = answer
+        .as_ref()
+        .and_then(|answer| answers.verify(day, part, answer));
+
+    print_result(
+        &outcome,
+        &part_str,
+        &format!(
+            "{}{}{}",
+            format_verification(correct, answers.get(day, part)),
+            format_duration(&stats),
+            format_memory(mem.as_ref())
+        ),
+    );
+
+    PartReport {
+        day,
+        part,
+        answer,
+        duration_nanos: stats.median.as_nanos(),
+        samples: stats.samples,
+        success: outcome.answer().is_some(),
+        correct,
+        error: outcome.error().map(ToString::to_string),
+        mem,
+    }
+}
+
+/// The main implementation of a part along with the runs of its variants, i.e. alternative implementations
+/// that are passed to the `solution!` macro. Every variant must produce the same answer as the main implementation.
+pub struct Variants {
+    report: PartReport,
+    /// Names and median durations of all implementations, starting with the main one.
+    timings: Vec<(&'static str, Duration)>,
+    /// Names of the variants whose answer differs from the main implementation.
+    mismatches: Vec<&'static str>,
+}
+
+impl Variants {
+    /// `report` is the record of the main implementation `name`, see [`run_part`].
+    #[must_use]
+    pub fn new(name: &'static str, report: PartReport) -> Self {
+        Self {
+            timings: vec![(
+                name,
+                Duration::from_nanos(u64::try_from(report.duration_nanos).unwrap_or(u64::MAX)),
+            )],
+            report,
+            mismatches: vec![],
+        }
+    }
+
+    /// Runs a variant of the part and checks its answer against the main implementation.
+    pub fn run<I: Clone, R: IntoOutcome>(
+        &mut self,
+        name: &'static str,
+        func: impl Fn(I) -> R,
+        input: I,
+        config: &RunConfig,
+    ) {
+        let label = format!("  {name}");
+
+        let (result, stats, mem) = run_timed(func, input, config, |result| {
+            print_result(&result.outcome(), &label, "");
+        });
+
+        let outcome = result.outcome();
+        let expected = self.report.answer.as_deref();
+        let agrees = outcome.answer() == expected;
+
+        print_result(
+            &outcome,
+            &label,
+            &format!(
+                "{}{}{}",
+                format_verification(Some(agrees), expected),
+                format_duration(&stats),
+                format_memory(mem.as_ref())
+            ),
+        );
+
+        self.timings.push((name, stats.median));
+
+        if !agrees {
+            self.mismatches.push(name);
+        }
+    }
+
+    /// Prints how the variants compare and submits the answer if requested.
+    /// If a variant disagrees with the main implementation, the part is marked as failed and not submitted.
+    #[must_use]
+    pub fn finish(mut self, year: Year, day: Day, config: &RunConfig) -> PartReport {
+        let part = self.report.part;
+
+        if self.timings.len() > 1 {
+            println!("{ANSI_BOLD}Part {part} variants:{ANSI_RESET}");
+            print!("{}", format_comparison(&self.timings));
+        }
+
+        if !self.mismatches.is_empty() {
+            let error = format!(
+                "variants disagree with {}: {}",
+                self.timings[0].0,
+                self.mismatches.join(", ")
+            );
+            println!("Part {part}: ✖ error: {error}");
+            self.report.error = Some(error);
+        }
+
+        if let Some(answer) = self
+            .report
+            .answer
+            .as_ref()
+            .filter(|_| config.submit == Some(part))
+        {
+            if config.custom_input {
+                eprintln!("Not submitting, the answer was not computed from the puzzle input.");
+            } else if let Some(error) = &self.report.error {
+                eprintln!("Not submitting, {error}.");
+            } else {
+                submit_result(answer, year, day, part, config.force);
+            }
+        }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        self.report
     }
 }
 
+/// Runs the parse function of a solution and prints how long it took. Returns the parsed input, or [`None`] if
+/// parsing failed, along with a record of the run. The record has part number `0`.
+pub fn run_parse<'a, T, E: Debug>(
+    func: impl Fn(&'a str) -> Result<T, E>,
+    input: &'a str,
+    day: Day,
+    config: &RunConfig,
+) -> (Option<T>, PartReport) {
+    let (result, stats, mem) = run_timed(func, input, config, |_| print!("Parse:"));
+
+    let error = result.as_ref().err().map(|e| format!("{e:?}"));
+    let details = format!("{}{}", format_duration(&stats), format_memory(mem.as_ref()));
+
+    print!("\r");
+    match &error {
+        Some(error) => println!("Parse: ✖ error: {error}{details}"),
+        None => println!("Parse:{details}"),
+    }
+
+    let report = PartReport {
+        day,
+        part: 0,
+        answer: None,
+        duration_nanos: stats.median.as_nanos(),
+        samples: stats.samples,
+        success: error.is_none(),
+        correct: None,
+        error,
+        mem,
+    };
+
+    (result.ok(), report)
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (see [`BenchOptions`] for the number of warmup runs and samples.)
+///
+/// For `--mem` runs, the allocations of the first execution are counted.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    config: &RunConfig,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Stats, Option<MemStats>) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (result, mem) = if config.mem {
+        memory::measure(|| func(input.clone()))
+    } else {
+        (func(input.clone()), None)
+    };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if config.time {
+        bench(func, input, &base_time, &config.bench)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, mem)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &BenchOptions,
+) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..options.warmup_count(base_time) {
+        func(input.clone());
+    }
+
+    let bench_iterations = options.sample_count(base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,34 +356,87 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers).unwrap_or_else(|| Stats::single(*base_time))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Marks an answer as correct, or as incorrect along with the expected answer.
+#[must_use]
+pub fn format_verification(correct: Option<bool>, expected: Option<&str>) -> String {
+    match (correct, expected) {
+        (Some(true), _) => " ✔".into(),
+        (Some(false), Some(expected)) => format!(" ✘ (expected {expected})"),
+        _ => String::new(),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} @ {} samples) [min {:.1?}, p95 {:.1?}, σ {:.1?}, {} outliers]",
+            stats.median, stats.samples, stats.min, stats.p95, stats.stddev, stats.outliers
+        )
+    }
+}
+
+/// Formats a table of the median durations of a part's implementations, relative to the fastest one.
+fn format_comparison(timings: &[(&str, Duration)]) -> String {
+    let fastest = timings
+        .iter()
+        .map(|(_, duration)| *duration)
+        .min()
+        .unwrap_or_default()
+        .max(Duration::from_nanos(1));
+
+    let durations: Vec<String> = timings
+        .iter()
+        .map(|(_, duration)| format!("{duration:.1?}"))
+        .collect();
+
+    let name_width = timings
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(["variant".len()])
+        .max()
+        .unwrap_or_default();
+
+    let duration_width = durations
+        .iter()
+        .map(String::len)
+        .chain(["median".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!(
+        "  {:<name_width$}  {:>duration_width$}  relative\n",
+        "variant", "median"
+    );
+
+    for ((name, duration), duration_str) in timings.iter().zip(&durations) {
+        let relative = format!("{:.2}x", duration.as_secs_f64() / fastest.as_secs_f64());
+        let fastest_str = if *duration <= fastest {
+            " (fastest)"
+        } else {
+            ""
+        };
+        table.push_str(&format!(
+            "  {name:<name_width$}  {duration_str:>duration_width$}  {relative:>8}{fastest_str}\n"
+        ));
     }
+
+    table
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_memory(mem: Option<&MemStats>) -> String {
+    mem.map_or_else(String::new, |mem| format!(" [{mem}]"))
+}
+
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
+    match outcome {
+        Outcome::Answer(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -116,7 +456,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,44 +464,91 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(error) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ error: {error}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution via the client selected by `AOC_CLIENT`.
+/// Answers that earlier submissions have shown to be wrong are not submitted, unless `force` is set.
+/// The response is classified and recorded, see [`submissions::record`].
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    force: bool,
+) -> Option<Verdict> {
+    let client = match aoc_client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+    let answer = result.to_string();
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
+    if !force {
+        let previous = submissions::load(year).unwrap_or_else(|e| {
+            eprintln!("Failed to read earlier submissions: {e:?}");
+            vec![]
+        });
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+        if let Some(rejection) = submissions::check(&previous, day, part, &answer) {
+            eprintln!("Not submitting, {rejection} Append `--force` to submit anyway.");
+            return None;
+        }
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    println!("Submitting result via {}...", client.name());
+    let output = match client.submit(year, day, part, &answer) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("failed to submit: {e}");
+            return None;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    let verdict = Verdict::from_output(&output);
+
+    if verdict == Verdict::Unknown {
+        println!("{output}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    println!("{verdict}");
+
+    if let Err(e) = submissions::record(year, day, part, &answer, &verdict) {
+        eprintln!("Failed to record submission: {e:?}");
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(verdict)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_comparison;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_comparison() {
+        let timings = [
+            ("part_one", Duration::from_millis(45)),
+            ("part_one_intervals", Duration::from_micros(1500)),
+        ];
+
+        assert_eq!(
+            format_comparison(&timings),
+            "  variant             median  relative
+  part_one            45.0ms    30.00x
+  part_one_intervals   1.5ms     1.00x (fastest)
+"
+        );
+    }
 }
@@ -0,0 +1,435 @@
+/// Module that renders the templates used by `cargo scaffold`.
+/// Templates are looked up in `.templates/{name}.rs` first, so that the built-in templates can be overridden
+/// and new ones added. Placeholders are written as `{{name}}`, see [`Context`] for the supported names.
+use std::{fmt::Display, fs, io};
+
+use crate::template::paths;
+use crate::{Day, Year};
+
+pub const TEMPLATES_DIR: &str = ".templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_RETURN_TYPE: &str = "u32";
+
+/// Scaffolds tests with inline `rstest` cases.
+const DEFAULT: &str = r#"advent_of_code::solution!({{year}}, {{day}});
+
+pub fn part_one(input: &str) -> Option<{{return_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{return_type}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("input", 42)]
+    fn test_part_one(#[case] input: &str, #[case] expected: {{return_type}}) {
+        let result = part_one(input);
+        assert_eq!(result, Some(expected));
+    }
+
+    #[rstest]
+    #[case("input", 42)]
+    fn test_part_two(#[case] input: &str, #[case] expected: {{return_type}}) {
+        let result = part_two(input);
+        assert_eq!(result, Some(expected));
+    }
+}
+"#;
+
+/// Scaffolds tests that read the example file.
+const FILE: &str = r#"advent_of_code::solution!({{year}}, {{day}});
+
+pub fn part_one(input: &str) -> Option<{{return_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{return_type}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+static BUILT_IN: [(&str, &str); 2] = [(DEFAULT_TEMPLATE, DEFAULT), ("file", FILE)];
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    /// Neither `.templates` nor the built-in templates contain a template of this name.
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::NotFound(name) => write!(
+                f,
+                "template \"{name}\" not found. Available templates: {}",
+                names().join(", ")
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Values available to templates.
+///
+/// | placeholder        | example                      |
+/// | ------------------ | ---------------------------- |
+/// | `{{year}}`         | `2015`                       |
+/// | `{{day}}`          | `9`                          |
+/// | `{{day_padded}}`   | `09`                         |
+/// | `{{return_type}}`  | `u32`                        |
+/// | `{{input_path}}`   | `data/2015/inputs/09.txt`    |
+/// | `{{example_path}}` | `data/2015/examples/09.txt`  |
+pub struct Context<'a> {
+    pub year: Year,
+    pub day: Day,
+    pub return_type: &'a str,
+}
+
+impl Context<'_> {
+    fn get(&self, placeholder: &str) -> Option<String> {
+        match placeholder {
+            "year" => Some(self.year.to_string()),
+            "day" => Some(self.day.into_inner().to_string()),
+            "day_padded" => Some(self.day.to_string()),
+            "return_type" => Some(self.return_type.to_string()),
+            "input_path" => Some(paths::input_path(self.year, self.day)),
+            "example_path" => Some(paths::example_path(self.year, self.day)),
+            _ => None,
+        }
+    }
+}
+
+/// A scaffold template, read from `.templates` or built in.
+pub struct Template {
+    pub name: String,
+    source: String,
+}
+
+impl Template {
+    pub fn load(name: &str) -> Result<Self, Error> {
+        let source = match fs::read_to_string(paths::template_path(name)) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BUILT_IN
+                .iter()
+                .find(|(built_in, _)| *built_in == name)
+                .map(|(_, source)| source.to_string())
+                .ok_or_else(|| Error::NotFound(name.to_string()))?,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            source,
+        })
+    }
+
+    /// Whether solutions scaffolded from this template read the example file.
+    #[must_use]
+    pub fn uses_examples(&self) -> bool {
+        self.source.contains("\"examples\"") || self.source.contains("{{example_path}}")
+    }
+
+    pub fn render(&self, context: &Context) -> Result<String, Error> {
+        render(&self.source, context)
+    }
+}
+
+/// Names of all available templates, built-in templates first.
+#[must_use]
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN.iter().map(|(name, _)| name.to_string()).collect();
+
+    let mut custom: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .filter(|name| !names.contains(name))
+        .collect();
+    custom.sort();
+
+    names.append(&mut custom);
+    names
+}
+
+/// Adds the tests of a freshly rendered template that are missing from the `tests` module of an existing solution.
+/// Everything else, in particular the solution functions, is left untouched. Returns the updated module and the
+/// names of the added tests.
+#[must_use]
+pub fn merge_tests(module: &str, rendered: &str) -> (String, Vec<String>) {
+    let Some((rendered_start, rendered_end)) = tests_module(rendered) else {
+        return (module.to_string(), vec![]);
+    };
+    let tests = test_items(&rendered[rendered_start..rendered_end]);
+
+    let Some((start, end)) = tests_module(module) else {
+        // the solution has no tests at all, add the whole module of the template.
+        let tests_start = rendered
+            .find("#[cfg(test)]")
+            .or_else(|| rendered.find("mod tests"))
+            .unwrap_or_default();
+        let names = tests.into_iter().map(|(name, _)| name).collect();
+        let separator = if module.ends_with('\n') { "\n" } else { "\n\n" };
+        return (
+            format!("{module}{separator}{}", &rendered[tests_start..]),
+            names,
+        );
+    };
+
+    let existing = &module[start..end];
+    let (missing, names): (Vec<_>, Vec<_>) = tests
+        .into_iter()
+        .filter(|(name, _)| !existing.contains(&format!("fn {name}(")))
+        .map(|(name, item)| (item, name))
+        .unzip();
+
+    if missing.is_empty() {
+        return (module.to_string(), vec![]);
+    }
+
+    let body = existing.trim_end();
+    let insertion: String = missing.iter().map(|item| format!("\n\n{item}")).collect();
+    (
+        format!("{}{body}{insertion}\n{}", &module[..start], &module[end..]),
+        names,
+    )
+}
+
+/// Returns the range of the body of the `tests` module, i.e. between its braces.
+fn tests_module(source: &str) -> Option<(usize, usize)> {
+    let start = source.find("mod tests")?;
+    let open = start + source[start..].find('{')?;
+    Some((open + 1, matching_brace(source, open)?))
+}
+
+/// Returns the position of the brace that closes the brace at `open`.
+pub(crate) fn matching_brace(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the name and source (including attributes and indentation) of all test functions.
+fn test_items(source: &str) -> Vec<(String, String)> {
+    let mut items = vec![];
+    let mut offset = 0;
+
+    while let Some(pos) = source[offset..].find("fn ") {
+        let fn_start = offset + pos;
+        let name_start = fn_start + 3;
+        let Some(name_end) = source[name_start..].find('(').map(|p| name_start + p) else {
+            break;
+        };
+        let Some(end) = source[fn_start..]
+            .find('{')
+            .and_then(|p| matching_brace(source, fn_start + p))
+        else {
+            break;
+        };
+
+        // include the attributes on the lines above the function.
+        let mut start = source[..fn_start].rfind('\n').map_or(0, |p| p + 1);
+        while let Some(line_start) = source[..start.saturating_sub(1)].rfind('\n') {
+            if !source[line_start + 1..start].trim_start().starts_with("#[") {
+                break;
+            }
+            start = line_start + 1;
+        }
+
+        let item = &source[start..=end];
+        if item.contains("test]") {
+            items.push((
+                source[name_start..name_end].trim().to_string(),
+                item.to_string(),
+            ));
+        }
+        offset = end + 1;
+    }
+
+    items
+}
+
+/// Replaces all `{{name}}` placeholders. Unknown placeholders are an error, braces that do not
+/// enclose a name (e.g. `format!("{{}}")`) are kept as they are.
+fn render(source: &str, context: &Context) -> Result<String, Error> {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        match after.find("}}").map(|end| (after[..end].trim(), end)) {
+            Some((name, end))
+                if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+            {
+                let value = context
+                    .get(name)
+                    .ok_or_else(|| Error::Parser(format!("unknown placeholder \"{name}\"")))?;
+                output.push_str(&value);
+                rest = &after[end + 2..];
+            }
+            _ => {
+                output.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{merge_tests, render, Context, Template};
+    use crate::{day, year};
+
+    fn context() -> Context<'static> {
+        Context {
+            year: year!(2015),
+            day: day!(9),
+            return_type: "u64",
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        assert_eq!(
+            render(
+                "{{year}}-{{ day }}-{{day_padded}}: {{return_type}} {{example_path}} {{input_path}}",
+                &context()
+            )
+            .unwrap(),
+            "2015-9-09: u64 data/2015/examples/09.txt data/2015/inputs/09.txt"
+        );
+    }
+
+    #[test]
+    fn keeps_format_braces() {
+        assert_eq!(
+            render(r#"format!("{{}} {{{{")"#, &context()).unwrap(),
+            r#"format!("{{}} {{{{")"#
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_unknown_placeholders() {
+        render("{{month}}", &context()).unwrap();
+    }
+
+    #[test]
+    fn renders_built_in_templates() {
+        let default = Template::load("default").unwrap();
+        assert!(!default.uses_examples());
+        assert!(default
+            .render(&context())
+            .unwrap()
+            .starts_with("advent_of_code::solution!(2015, 9);"));
+
+        let file = Template::load("file").unwrap();
+        assert!(file.uses_examples());
+        assert!(file
+            .render(&context())
+            .unwrap()
+            .contains("fn part_two(input: &str) -> Option<u64>"));
+
+        assert!(Template::load("missing").is_err());
+    }
+
+    #[test]
+    fn merges_missing_tests() {
+        let rendered = Template::load("file").unwrap().render(&context()).unwrap();
+        let module = "advent_of_code::solution!(2015, 9);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(input.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one(\"abc\"), Some(3));
+    }
+}
+";
+
+        let (merged, added) = merge_tests(module, &rendered);
+        assert_eq!(added, vec!["test_part_two"]);
+        assert!(merged.starts_with(&module[..module.find("#[cfg(test)]").unwrap()]));
+        assert!(merged.contains("assert_eq!(part_one(\"abc\"), Some(3));"));
+        assert!(merged.ends_with(
+            "        assert_eq!(result, None);
+    }
+}
+"
+        ));
+        assert_eq!(merged.matches("fn test_part_one").count(), 1);
+
+        let (unchanged, added) = merge_tests(&merged, &rendered);
+        assert_eq!(unchanged, merged);
+        assert!(added.is_empty());
+    }
+
+    #[test]
+    fn adds_missing_tests_module() {
+        let rendered = Template::load("file").unwrap().render(&context()).unwrap();
+        let module = "pub fn part_one(input: &str) -> Option<u64> {
+    None
+}
+";
+        let (merged, added) = merge_tests(module, &rendered);
+        assert_eq!(added, vec!["test_part_one", "test_part_two"]);
+        assert!(merged.starts_with(&format!("{module}\n#[cfg(test)]\nmod tests {{")));
+    }
+}
@@ -0,0 +1,438 @@
+/// Module that classifies the responses to submitted answers and keeps a log of all evaluated submissions in `data/{year}/submissions.txt`.
+/// Correct answers are additionally stored in the answers file (see [`crate::template::answers`]).
+/// Answers are escaped like in the answers file, so that multi-line answers do not break the log.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::answers::{self, Answers};
+use crate::template::paths;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    Answers(answers::Error),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<answers::Error> for Error {
+    fn from(e: answers::Error) -> Self {
+        Error::Answers(e)
+    }
+}
+
+/// The response of advent of code to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently. Contains the time left to wait, if it could be parsed.
+    RateLimited(Option<Duration>),
+    /// The part has already been solved, or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Classifies the output of a submission.
+    #[must_use]
+    pub fn from_output(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            Verdict::Correct
+        } else if output.contains("answer too recently") {
+            Verdict::RateLimited(parse_wait_time(output))
+        } else if output.contains("not the right answer") {
+            if output.contains("too high") {
+                Verdict::TooHigh
+            } else if output.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if output.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether advent of code evaluated the answer. Only evaluated submissions are logged.
+    #[must_use]
+    pub fn is_evaluated(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+        )
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited(_) => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "⭐ That's the right answer!"),
+            Verdict::TooHigh => {
+                write!(f, "✖ That's not the right answer, your answer is too high.")
+            }
+            Verdict::TooLow => write!(f, "✖ That's not the right answer, your answer is too low."),
+            Verdict::Wrong => write!(f, "✖ That's not the right answer."),
+            Verdict::RateLimited(Some(wait)) => write!(
+                f,
+                "⏳ You submitted an answer too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            Verdict::RateLimited(None) => {
+                write!(
+                    f,
+                    "⏳ You submitted an answer too recently, wait before trying again."
+                )
+            }
+            Verdict::WrongLevel => write!(f, "✖ This part is already solved or not unlocked yet."),
+            Verdict::Unknown => write!(f, "? Could not understand the response of advent of code."),
+        }
+    }
+}
+
+/// Parses a wait time like `You have 1m 20s left to wait.` from the output.
+fn parse_wait_time(output: &str) -> Option<Duration> {
+    let start = output.find("You have ")? + "You have ".len();
+    let end = start + output[start..].find(" left to wait")?;
+
+    output[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// An evaluated submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Time of the submission in seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+fn format_submission(s: &Submission) -> String {
+    format!(
+        "{}\t{}-{}\t{}\t{}",
+        s.timestamp,
+        s.day,
+        s.part,
+        s.verdict.as_str(),
+        answers::escape(&s.answer)
+    )
+}
+
+fn parse_submission(line: &str) -> Result<Submission, Error> {
+    let err = || Error::Parser(format!("malformed submission line: {line}"));
+
+    let [timestamp, key, verdict, answer] = line.splitn(4, '\t').collect::<Vec<_>>()[..] else {
+        return Err(err());
+    };
+    let (day, part) = key.split_once('-').ok_or_else(err)?;
+
+    Ok(Submission {
+        timestamp: timestamp.parse().map_err(|_| err())?,
+        day: day.parse().map_err(|_| err())?,
+        part: part.parse().map_err(|_| err())?,
+        verdict: Verdict::from_str(verdict).ok_or_else(err)?,
+        answer: answers::unescape(answer),
+    })
+}
+
+fn parse_content(s: &str) -> Result<Vec<Submission>, Error> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(parse_submission)
+        .collect()
+}
+
+fn submissions_path(year: Year) -> String {
+    format!("{}/submissions.txt", paths::data_dir(year))
+}
+
+/// Loads the log of evaluated submissions. A missing file is treated as an empty log.
+pub fn load(year: Year) -> Result<Vec<Submission>, Error> {
+    match fs::read_to_string(submissions_path(year)) {
+        Ok(s) => parse_content(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A reason not to submit an answer, learned from earlier submissions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The same answer was submitted before and was not correct.
+    AlreadyRejected(Verdict),
+    /// The answer is at least as high as an answer that was too high.
+    TooHigh(String),
+    /// The answer is at most as low as an answer that was too low.
+    TooLow(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyRejected(verdict) => write!(
+                f,
+                "this answer was already submitted and was rejected as \"{}\".",
+                verdict.as_str()
+            ),
+            Rejection::TooHigh(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which was too high."
+                )
+            }
+            Rejection::TooLow(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which was too low."
+                )
+            }
+        }
+    }
+}
+
+/// Checks an answer against earlier submissions of the same day and part.
+/// Returns a [`Rejection`] if the answer is known to be wrong.
+#[must_use]
+pub fn check(submissions: &[Submission], day: Day, part: u8, answer: &str) -> Option<Rejection> {
+    let answer = answer.trim();
+    let previous: Vec<&Submission> = submissions
+        .iter()
+        .filter(|s| s.day == day && s.part == part)
+        .collect();
+
+    if let Some(s) = previous
+        .iter()
+        .find(|s| s.answer == answer && s.verdict != Verdict::Correct)
+    {
+        return Some(Rejection::AlreadyRejected(s.verdict.clone()));
+    }
+
+    let value: i128 = answer.parse().ok()?;
+    let numeric = |verdict: Verdict| {
+        previous
+            .iter()
+            .filter(move |s| s.verdict == verdict)
+            .filter_map(|s| s.answer.parse::<i128>().ok())
+    };
+
+    if let Some(bound) = numeric(Verdict::TooHigh).min().filter(|&b| value >= b) {
+        return Some(Rejection::TooHigh(bound.to_string()));
+    }
+
+    if let Some(bound) = numeric(Verdict::TooLow).max().filter(|&b| value <= b) {
+        return Some(Rejection::TooLow(bound.to_string()));
+    }
+
+    None
+}
+
+/// Records the verdict for a submitted answer.
+/// Evaluated submissions are appended to the log, correct answers are also added to the answers file.
+pub fn record(
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+    verdict: &Verdict,
+) -> Result<(), Error> {
+    if !verdict.is_evaluated() {
+        return Ok(());
+    }
+
+    let submission = Submission {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        day,
+        part,
+        verdict: verdict.clone(),
+        answer: answer.trim().into(),
+    };
+
+    fs::create_dir_all(paths::data_dir(year))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(submissions_path(year))?;
+    writeln!(file, "{}", format_submission(&submission))?;
+
+    if *verdict == Verdict::Correct {
+        let mut answers = Answers::load(year)?;
+        answers.set(day, part, answer);
+        answers.save(year)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, format_submission, parse_content, Rejection, Submission, Verdict};
+    use crate::day;
+    use std::time::Duration;
+
+    #[test]
+    fn classifies_verdicts() {
+        assert_eq!(
+            Verdict::from_output("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer. If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_output("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+        assert_eq!(Verdict::from_output("Error: HTTP 500"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn classifies_rate_limits() {
+        assert_eq!(
+            Verdict::from_output("You gave an answer too recently. You have 1m 20s left to wait."),
+            Verdict::RateLimited(Some(Duration::from_secs(80)))
+        );
+        assert_eq!(
+            Verdict::from_output("You gave an answer too recently. You have 35s left to wait."),
+            Verdict::RateLimited(Some(Duration::from_secs(35)))
+        );
+        assert_eq!(
+            Verdict::from_output("You gave an answer too recently."),
+            Verdict::RateLimited(None)
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submission = Submission {
+            timestamp: 1_700_000_000,
+            day: day!(4),
+            part: 2,
+            verdict: Verdict::TooLow,
+            answer: "12 34".into(),
+        };
+        let s = format_submission(&submission);
+        assert_eq!(s, "1700000000\t04-2\ttoo_low\t12 34");
+        assert_eq!(parse_content(&s).unwrap(), vec![submission]);
+    }
+
+    #[test]
+    fn roundtrips_multi_line_submissions() {
+        let submission = Submission {
+            timestamp: 1_700_000_000,
+            day: day!(8),
+            part: 2,
+            verdict: Verdict::Wrong,
+            answer: "#..#\n####\t#".into(),
+        };
+        let s = format_submission(&submission);
+        assert_eq!(s, "1700000000\t08-2\twrong\t#..#\\n####\\t#");
+        assert_eq!(parse_content(&s).unwrap(), vec![submission]);
+    }
+
+    fn submission(part: u8, verdict: Verdict, answer: &str) -> Submission {
+        Submission {
+            timestamp: 0,
+            day: day!(1),
+            part,
+            verdict,
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let submissions = vec![
+            submission(1, Verdict::Wrong, "abc"),
+            submission(1, Verdict::TooHigh, "100"),
+            submission(1, Verdict::TooHigh, "80"),
+            submission(1, Verdict::TooLow, "10"),
+            submission(2, Verdict::TooLow, "500"),
+        ];
+
+        assert_eq!(
+            check(&submissions, day!(1), 1, "abc"),
+            Some(Rejection::AlreadyRejected(Verdict::Wrong))
+        );
+        assert_eq!(
+            check(&submissions, day!(1), 1, "100"),
+            Some(Rejection::AlreadyRejected(Verdict::TooHigh))
+        );
+        assert_eq!(
+            check(&submissions, day!(1), 1, "90"),
+            Some(Rejection::TooHigh("80".into()))
+        );
+        assert_eq!(
+            check(&submissions, day!(1), 1, "-3"),
+            Some(Rejection::TooLow("10".into()))
+        );
+        assert_eq!(check(&submissions, day!(1), 1, "42"), None);
+        assert_eq!(check(&submissions, day!(1), 1, "xyz"), None);
+        assert_eq!(
+            check(&submissions, day!(1), 2, "90"),
+            Some(Rejection::TooLow("500".into()))
+        );
+        assert_eq!(check(&submissions, day!(2), 1, "abc"), None);
+    }
+}